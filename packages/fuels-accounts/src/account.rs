@@ -305,8 +305,9 @@ pub trait Account: ViewOnlyAccount {
             .get_asset_inputs_for_amount(BASE_ASSET_ID, amount)
             .await?;
 
-        let mut tb = ScriptTransactionBuilder::prepare_message_to_output(
+        let mut tb = ScriptTransactionBuilder::prepare_message_to_output_with_change(
             to.into(),
+            self.address().into(),
             amount,
             inputs,
             tx_policies,
@@ -335,10 +336,10 @@ mod tests {
     use std::str::FromStr;
 
     use fuel_crypto::{Message, SecretKey};
-    use fuel_tx::{Address, Output, Transaction as FuelTransaction};
+    use fuel_tx::{Address, Output};
     use fuels_core::types::{
         transaction::Transaction,
-        transaction_builders::{DryRunner, NetworkInfo},
+        transaction_builders::{MockDryRunner, NetworkInfo},
     };
     use rand::{rngs::StdRng, RngCore, SeedableRng};
 
@@ -380,15 +381,6 @@ mod tests {
         Ok(())
     }
 
-    struct MockDryRunner;
-
-    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-    impl DryRunner for MockDryRunner {
-        async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
-            Ok(0)
-        }
-    }
-
     #[tokio::test]
     async fn sign_tx_and_verify() -> std::result::Result<(), Box<dyn std::error::Error>> {
         // ANCHOR: sign_tx
@@ -430,7 +422,7 @@ mod tests {
         // Sign the transaction
         wallet.sign_transaction(&mut tb); // Add the private key to the transaction builder
                                           // ANCHOR_END: sign_tx
-        let tx = tb.build(MockDryRunner).await?; // Resolve signatures and add corresponding witness indexes
+        let tx = tb.build(MockDryRunner::default()).await?; // Resolve signatures and add corresponding witness indexes
 
         // Extract the signature from the tx witnesses
         let bytes = <[u8; Signature::LEN]>::try_from(tx.witnesses().first().unwrap().as_ref())?;
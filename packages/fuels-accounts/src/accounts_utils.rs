@@ -26,9 +26,12 @@ pub async fn calculate_missing_base_amount(
         .await?
         .ok_or(error!(InvalidData, "Error calculating TransactionFee"))?;
 
-    let available_amount = available_base_amount(tb);
+    let available_amount = available_base_amount(tb)?;
 
-    let total_used = transaction_fee.max_fee() + used_base_amount;
+    let total_used = transaction_fee
+        .max_fee()
+        .checked_add(used_base_amount)
+        .ok_or(error!(InvalidData, "sum of amounts overflows `u64`"))?;
     let missing_amount = if total_used > available_amount {
         total_used - available_amount
     } else if !is_consuming_utxos(tb) {
@@ -42,14 +45,18 @@ pub async fn calculate_missing_base_amount(
     Ok(missing_amount)
 }
 
-fn available_base_amount(tb: &impl TransactionBuilder) -> u64 {
+fn available_base_amount(tb: &impl TransactionBuilder) -> Result<u64> {
     tb.inputs()
         .iter()
         .filter_map(|input| match (input.amount(), input.asset_id()) {
             (Some(amount), Some(asset_id)) if asset_id == BASE_ASSET_ID => Some(amount),
             _ => None,
         })
-        .sum()
+        .try_fold(0u64, |total, amount| {
+            total
+                .checked_add(amount)
+                .ok_or(error!(InvalidData, "sum of amounts overflows `u64`"))
+        })
 }
 
 fn is_consuming_utxos(tb: &impl TransactionBuilder) -> bool {
@@ -10,7 +10,10 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use fuel_core_client::client::{
     pagination::{PageDirection, PaginatedResult, PaginationRequest},
-    types::{balance::Balance, contract::ContractBalance, TransactionStatus},
+    types::{
+        balance::Balance, contract::ContractBalance, message::MessageStatus as ClientMessageStatus,
+        TransactionStatus,
+    },
 };
 use fuel_tx::{
     AssetId, ConsensusParameters, Receipt, ScriptExecutionResult, Transaction as FuelTransaction,
@@ -29,11 +32,13 @@ use fuels_core::{
         coin::Coin,
         coin_type::CoinType,
         errors::{error, Error, Result},
-        message::Message,
+        message::{Message, MessageStatus},
         message_proof::MessageProof,
         node_info::NodeInfo,
         transaction::Transaction,
-        transaction_builders::{DryRunner, NetworkInfo},
+        transaction_builders::{
+            DetailedDryRunner, DryRunner, MessageStatusQuerier, NetworkInfo, ResourceQuerier,
+        },
         transaction_response::TransactionResponse,
         tx_status::TxStatus,
     },
@@ -768,4 +773,49 @@ impl DryRunner for Provider {
         let gas_used = self.get_gas_used(&receipts);
         Ok((gas_used as f64 * (1.0 + tolerance as f64)) as u64)
     }
+
+    async fn dry_run_and_get_used_gas_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+        tolerance: f32,
+    ) -> Result<u64> {
+        let receipts = self.client.dry_run_opt(&tx, Some(true)).await?;
+        let gas_used = self.get_gas_used(&receipts);
+        Ok((gas_used as f64 * (1.0 + tolerance as f64)) as u64)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ResourceQuerier for Provider {
+    async fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>> {
+        Ok(self.client.coin(utxo_id).await?.map(Into::into))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl MessageStatusQuerier for Provider {
+    async fn message_status(&self, message: &Message) -> Result<MessageStatus> {
+        match self.client.message_status(&message.nonce).await? {
+            ClientMessageStatus::Unspent => Ok(MessageStatus::Unspent),
+            ClientMessageStatus::Spent => Ok(MessageStatus::Spent),
+            ClientMessageStatus::NotFound => Err(error!(
+                InvalidData,
+                "message with nonce `{}` was not found", message.nonce
+            )),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl DetailedDryRunner for Provider {
+    async fn dry_run_and_get_receipts(&self, tx: FuelTransaction) -> Result<Vec<Receipt>> {
+        Ok(self.client.dry_run_opt(&tx, Some(false)).await?)
+    }
+
+    async fn dry_run_and_get_receipts_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<Receipt>> {
+        Ok(self.client.dry_run_opt(&tx, Some(true)).await?)
+    }
 }
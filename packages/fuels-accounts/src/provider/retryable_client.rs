@@ -106,6 +106,10 @@ impl RetryableClient {
             .await
     }
 
+    pub async fn coin(&self, id: &UtxoId) -> io::Result<Option<types::Coin>> {
+        self.our_retry(|| self.client.coin(id)).await
+    }
+
     pub async fn coins_to_spend(
         &self,
         owner: &Address,
@@ -198,6 +202,10 @@ impl RetryableClient {
             .await
     }
 
+    pub async fn message_status(&self, nonce: &Nonce) -> io::Result<types::message::MessageStatus> {
+        self.our_retry(|| self.client.message_status(nonce)).await
+    }
+
     /// Request a merkle proof of an output message.
     pub async fn message_proof(
         &self,
@@ -15,6 +15,7 @@ mod core;
 pub mod enum_variants;
 pub mod errors;
 pub mod param_types;
+pub mod predicate_artifact;
 pub mod transaction_builders;
 pub mod tx_status;
 pub mod unresolved_bytes;
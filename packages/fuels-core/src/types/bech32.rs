@@ -6,6 +6,7 @@ use std::{
 use bech32::{FromBase32, ToBase32, Variant::Bech32m};
 use fuel_tx::{Address, Bytes32, ContractId, ContractIdExt};
 use fuel_types::AssetId;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::types::{
     errors::{Error, Result},
@@ -76,6 +77,26 @@ macro_rules! bech32type {
                 write!(f, "{}", encoding)
             }
         }
+
+        impl Serialize for $i {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $i {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let encoded: String = Deserialize::deserialize(deserializer)?;
+
+                encoded.parse().map_err(de::Error::custom)
+            }
+        }
     };
 }
 
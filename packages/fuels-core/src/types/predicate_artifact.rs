@@ -0,0 +1,181 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use fuel_abi_types::abi::program::{ProgramABI, TypeApplication, TypeDeclaration};
+
+use crate::{
+    codec::ABIEncoder,
+    round_up_to_word_alignment,
+    types::{
+        errors::{error, Result},
+        param_types::ParamType,
+        unresolved_bytes::UnresolvedBytes,
+        Token,
+    },
+    Configurables,
+};
+
+/// Compiled predicate as output by `forc build`, i.e. its bytecode and JSON ABI.
+#[derive(Debug, Clone)]
+pub struct PredicateArtifact {
+    code: Vec<u8>,
+    abi: ProgramABI,
+}
+
+/// Value of the configurable constant or of the `main` argument called `name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigurableValue {
+    name: String,
+    value: Token,
+}
+
+impl ConfigurableValue {
+    pub fn new(name: impl Into<String>, value: Token) -> Self {
+        Self {
+            name: name.into(),
+            value,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Token {
+        &self.value
+    }
+}
+
+impl PredicateArtifact {
+    pub fn new(code: Vec<u8>, abi: ProgramABI) -> Self {
+        Self { code, abi }
+    }
+
+    /// Loads the bytecode from `binary_filepath` and the JSON ABI from `abi_filepath`.
+    pub fn load_from(
+        binary_filepath: impl AsRef<Path>,
+        abi_filepath: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let code = fs::read(binary_filepath)?;
+        let abi = serde_json::from_str(&fs::read_to_string(abi_filepath)?)?;
+
+        Ok(Self::new(code, abi))
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    pub fn abi(&self) -> &ProgramABI {
+        &self.abi
+    }
+
+    /// Returns the predicate code and data for `values`. Values named after a
+    /// configurable constant are written into the code at the offset given by the
+    /// ABI, the others are encoded, in the order of the `main` arguments, into the
+    /// data. Every `main` argument needs a value, configurables keep their default
+    /// when omitted.
+    pub fn code_and_data(
+        &self,
+        values: Vec<ConfigurableValue>,
+    ) -> Result<(Vec<u8>, UnresolvedBytes)> {
+        let main_inputs = &self
+            .abi
+            .functions
+            .iter()
+            .find(|function| function.name == "main")
+            .ok_or_else(|| error!(InvalidData, "predicate ABI has no `main` function"))?
+            .inputs;
+        let configurables = self.abi.configurables.as_deref().unwrap_or_default();
+        let type_lookup = self
+            .abi
+            .types
+            .iter()
+            .map(|decl| (decl.type_id, decl.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut seen = HashSet::new();
+        let mut offsets_with_data = vec![];
+        let mut args = vec![None; main_inputs.len()];
+        for ConfigurableValue { name, value } in values {
+            if !seen.insert(name.clone()) {
+                return Err(error!(InvalidData, "value for `{name}` was given twice"));
+            }
+
+            if let Some(configurable) = configurables.iter().find(|c| c.name == name) {
+                let data = self.encode_configurable(
+                    &name,
+                    configurable.offset,
+                    &configurable.application,
+                    &type_lookup,
+                    value,
+                )?;
+                offsets_with_data.push((configurable.offset, data));
+            } else if let Some(idx) = main_inputs.iter().position(|input| input.name == name) {
+                args[idx] = Some(value);
+            } else {
+                return Err(error!(
+                    InvalidData,
+                    "`{name}` is neither a configurable nor an argument of the predicate"
+                ));
+            }
+        }
+
+        let args = main_inputs
+            .iter()
+            .zip(args)
+            .map(|(input, arg)| {
+                arg.ok_or_else(|| {
+                    error!(
+                        InvalidData,
+                        "missing a value for the predicate argument `{}`", input.name
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut code = self.code.clone();
+        Configurables::new(offsets_with_data).update_constants_in(&mut code);
+
+        Ok((code, ABIEncoder::encode(&args)?))
+    }
+
+    // Encodes `value` the way the generated configurables do, i.e. word aligned, and
+    // checks that it matches the size of the configurable and fits in the code
+    fn encode_configurable(
+        &self,
+        name: &str,
+        offset: u64,
+        application: &TypeApplication,
+        type_lookup: &HashMap<usize, TypeDeclaration>,
+        value: Token,
+    ) -> Result<Vec<u8>> {
+        let param_type = ParamType::try_from_type_application(application, type_lookup)?;
+        let data = ABIEncoder::encode(&[value])?.resolve(0);
+
+        if param_type
+            .compute_encoding_in_bytes()
+            .is_some_and(|size| round_up_to_word_alignment(size) != data.len())
+        {
+            return Err(error!(
+                InvalidData,
+                "value of configurable `{name}` does not match its type `{param_type:?}`"
+            ));
+        }
+
+        let end = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| offset.checked_add(data.len()));
+        if end.map_or(true, |end| end > self.code.len()) {
+            return Err(error!(
+                InvalidData,
+                "configurable `{name}` at offset `{offset}` does not fit in the predicate code"
+            ));
+        }
+
+        Ok(data)
+    }
+}
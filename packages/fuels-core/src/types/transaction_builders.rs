@@ -1,45 +1,77 @@
 #![cfg(feature = "std")]
 
-use std::{cmp::max, collections::HashMap, iter::repeat_with};
+use std::{
+    cmp::max,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+    iter::repeat_with,
+    sync::{Arc, Mutex},
+};
 
 use async_trait::async_trait;
-use fuel_asm::{op, GTFArgs, RegId};
-use fuel_crypto::{Message as CryptoMessage, SecretKey, Signature};
+use fuel_asm::{op, GTFArgs, Instruction, RegId};
+use fuel_crypto::{Hasher, Message as CryptoMessage, PublicKey, SecretKey, Signature};
 use fuel_tx::{
-    field::{Inputs, Witnesses},
+    field::{
+        Inputs, Outputs, Policies as PoliciesField, Script as ScriptField, ScriptData,
+        ScriptGasLimit, Witnesses,
+    },
+    input::{
+        coin::CoinPredicate,
+        message::{MessageCoinPredicate, MessageDataPredicate},
+    },
     policies::{Policies, PolicyType},
-    Buildable, Chargeable, ConsensusParameters, Create, Input as FuelInput, Output, Script,
-    StorageSlot, Transaction as FuelTransaction, TransactionFee, TxPointer, UniqueIdentifier,
-    Witness,
+    Buildable, Chargeable, ConsensusParameters, Contract as FuelContract, Create, FeeParameters,
+    GasCosts, Input as FuelInput, Output, Receipt, Script, StorageSlot,
+    Transaction as FuelTransaction, TransactionFee, TxPointer, UniqueIdentifier, Witness,
+};
+use fuel_types::{
+    bytes::padded_len_usize,
+    canonical::{Deserialize, Serialize},
+    Bytes32, ChainId, Salt,
 };
-use fuel_types::{bytes::padded_len_usize, canonical::Serialize, Bytes32, ChainId, Salt};
+use itertools::Itertools;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::{
+    codec::ABIEncoder,
     constants::{
         BASE_ASSET_ID, DEFAULT_CREATE_WITNESS_LIMIT, DEFAULT_SCRIPT_WITNESS_LIMIT, WORD_SIZE,
     },
     offsets,
     types::{
-        bech32::Bech32Address,
+        bech32::{Bech32Address, FUEL_BECH32_HRP},
         chain_info::ChainInfo,
         coin::Coin,
         coin_type::CoinType,
+        coin_type_id::CoinTypeId,
         errors::{error, Result},
         input::Input,
-        message::Message,
+        message::{Message, MessageStatus},
         node_info::NodeInfo,
+        predicate_artifact::{ConfigurableValue, PredicateArtifact},
         transaction::{
             CreateTransaction, EstimablePredicates, ScriptTransaction, Transaction, TxPolicies,
         },
-        unresolved_bytes::UnresolvedBytes,
-        Address, AssetId, ContractId,
+        unresolved_bytes::{Data, UnresolvedBytes},
+        Address, AssetId, ContractId, Selector, Token, UtxoId,
     },
 };
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait DryRunner: Send + Sync {
     async fn dry_run_and_get_used_gas(&self, tx: FuelTransaction, tolerance: f32) -> Result<u64>;
+
+    /// Like `dry_run_and_get_used_gas` but the node validates the UTXOs and signatures
+    /// of the transaction. Runners that can not enable the validation fall back to
+    /// a regular dry run.
+    async fn dry_run_and_get_used_gas_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+        tolerance: f32,
+    ) -> Result<u64> {
+        self.dry_run_and_get_used_gas(tx, tolerance).await
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -47,6 +79,374 @@ impl<T: DryRunner> DryRunner for &T {
     async fn dry_run_and_get_used_gas(&self, tx: FuelTransaction, tolerance: f32) -> Result<u64> {
         (*self).dry_run_and_get_used_gas(tx, tolerance).await
     }
+
+    async fn dry_run_and_get_used_gas_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+        tolerance: f32,
+    ) -> Result<u64> {
+        (*self)
+            .dry_run_and_get_used_gas_with_utxo_validation(tx, tolerance)
+            .await
+    }
+}
+
+/// `DryRunner` that doesn't run anything and always reports `gas_used`, for tests
+/// that build transactions without a node.
+#[cfg(any(test, feature = "test-helpers"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockDryRunner {
+    pub gas_used: u64,
+}
+
+#[cfg(any(test, feature = "test-helpers"))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DryRunner for MockDryRunner {
+    async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
+        Ok(self.gas_used)
+    }
+}
+
+/// Source of spendable resources that are pulled on demand, e.g. by paging
+/// through the UTXOs of an account instead of fetching all of them up front.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ResourceStream: Send {
+    /// Returns the next resource or `None` once the stream is exhausted.
+    async fn next_resource(&mut self) -> Result<Option<CoinType>>;
+}
+
+/// Looks up resources by their id, e.g. when only the `UtxoId` of a coin is known.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ResourceQuerier: Send + Sync {
+    /// Returns the coin with the given `utxo_id` or `None` if it does not exist.
+    async fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>>;
+}
+
+/// Looks up whether messages can still be spent.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait MessageStatusQuerier: Send + Sync {
+    /// Fails if the message is unknown, e.g. because it was never relayed.
+    async fn message_status(&self, message: &Message) -> Result<MessageStatus>;
+}
+
+/// Exchange rates used to display fees in an asset other than the base asset.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait RateProvider: Send + Sync {
+    /// Amount of `target` one unit of `base` is worth.
+    async fn rate(&self, base: AssetId, target: AssetId) -> Result<f64>;
+}
+
+/// Source of gas prices following the current network conditions.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait GasPriceOracle: Send + Sync {
+    async fn suggested_gas_price(&self) -> Result<u64>;
+}
+
+/// `DryRunner` that also returns the receipts of the dry run.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait DetailedDryRunner: DryRunner {
+    async fn dry_run_and_get_receipts(&self, tx: FuelTransaction) -> Result<Vec<Receipt>>;
+
+    /// Like `dry_run_and_get_receipts` but the node validates the UTXOs and signatures
+    /// of the transaction. Runners that can not enable the validation fall back to
+    /// a regular dry run.
+    async fn dry_run_and_get_receipts_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<Receipt>> {
+        self.dry_run_and_get_receipts(tx).await
+    }
+
+    /// Contract storage slots the dry run would write. Fails by default since not
+    /// every node reports them.
+    async fn dry_run_and_get_storage_changes(
+        &self,
+        _tx: FuelTransaction,
+    ) -> Result<Vec<StorageChange>> {
+        Err(error!(
+            InvalidData,
+            "the dry runner does not report storage changes"
+        ))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: DetailedDryRunner> DetailedDryRunner for &T {
+    async fn dry_run_and_get_receipts(&self, tx: FuelTransaction) -> Result<Vec<Receipt>> {
+        (*self).dry_run_and_get_receipts(tx).await
+    }
+
+    async fn dry_run_and_get_receipts_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<Receipt>> {
+        (*self)
+            .dry_run_and_get_receipts_with_utxo_validation(tx)
+            .await
+    }
+
+    async fn dry_run_and_get_storage_changes(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<StorageChange>> {
+        (*self).dry_run_and_get_storage_changes(tx).await
+    }
+}
+
+/// Write to a contract storage slot observed during a dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    pub contract_id: ContractId,
+    pub key: Bytes32,
+    /// `None` if the slot was not set before the transaction.
+    pub old_value: Option<Bytes32>,
+    /// `None` if the transaction cleared the slot.
+    pub new_value: Option<Bytes32>,
+}
+
+/// Outcome of dry running a script without broadcasting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationResult {
+    pub receipts: Vec<Receipt>,
+    /// Data of the last `ReturnData` receipt, if any.
+    pub return_data: Option<Vec<u8>>,
+    /// `Log` and `LogData` receipts in the order they were emitted.
+    pub logs: Vec<Receipt>,
+    pub gas_used: u64,
+}
+
+impl SimulationResult {
+    fn from_receipts(receipts: Vec<Receipt>) -> Result<Self> {
+        let gas_used = receipts
+            .iter()
+            .rev()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                _ => None,
+            })
+            .ok_or(error!(
+                InvalidData,
+                "dry run did not return a `ScriptResult` receipt"
+            ))?;
+
+        let return_data = receipts.iter().rev().find_map(|receipt| match receipt {
+            Receipt::ReturnData { data, .. } => data.clone(),
+            _ => None,
+        });
+
+        let logs = receipts
+            .iter()
+            .filter(|receipt| matches!(receipt, Receipt::Log { .. } | Receipt::LogData { .. }))
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            receipts,
+            return_data,
+            logs,
+            gas_used,
+        })
+    }
+}
+
+/// Overview of a transaction, e.g. to render an approval screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSummary {
+    /// Total amount of every asset held by the inputs.
+    pub input_amounts: BTreeMap<AssetId, u64>,
+    /// The coin outputs, i.e. the fixed amounts sent to a recipient.
+    pub outputs: Vec<OutputSummary>,
+    /// Recipient of the change of every asset.
+    pub change_recipients: BTreeMap<AssetId, Address>,
+    pub min_fee: u64,
+    pub max_fee: u64,
+    /// Gas limit of the whole transaction, i.e. the `script_gas_limit` plus the gas
+    /// charged for its bytes, inputs and predicates.
+    pub max_gas: u64,
+    pub policies: Policies,
+    /// Owners of the signatures added during build, sorted by witness index.
+    pub signers: Vec<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSummary {
+    pub to: Address,
+    pub asset_id: AssetId,
+    pub amount: u64,
+}
+
+/// What changed between two builders, see `diff`. Signatures are not compared so
+/// no secret key ends up in the diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuilderDiff {
+    pub added_inputs: Vec<Input>,
+    pub removed_inputs: Vec<Input>,
+    pub added_outputs: Vec<Output>,
+    pub removed_outputs: Vec<Output>,
+    pub added_witnesses: Vec<Witness>,
+    pub removed_witnesses: Vec<Witness>,
+    /// The old and the new value of every policy that differs.
+    pub changed_policies: Vec<(PolicyType, Option<u64>, Option<u64>)>,
+}
+
+impl BuilderDiff {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+// JSON description of the parts of a transaction that hold no secrets
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct TransactionComponents {
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    witnesses: Vec<Witness>,
+}
+
+/// `DryRunner` that caches the `gas_used` of every transaction it has seen.
+/// Estimating an unchanged transaction again, e.g. calling `fee_checked_from_tx`
+/// before `build`, reuses the prior result instead of dry running it once more.
+/// Results are keyed by the hash of the serialized transaction so any mutation
+/// leads to a new dry run.
+#[derive(Debug, Default)]
+pub struct CachedDryRunner<D> {
+    dry_runner: D,
+    cache: Mutex<HashMap<(Bytes32, u32), u64>>,
+}
+
+impl<D> CachedDryRunner<D> {
+    pub fn new(dry_runner: D) -> Self {
+        Self {
+            dry_runner,
+            cache: Default::default(),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D: DryRunner> DryRunner for CachedDryRunner<D> {
+    async fn dry_run_and_get_used_gas(&self, tx: FuelTransaction, tolerance: f32) -> Result<u64> {
+        let key = (Hasher::hash(tx.to_bytes()), tolerance.to_bits());
+
+        let cached_gas_used = self.cache.lock().expect("poisoned lock").get(&key).copied();
+        if let Some(gas_used) = cached_gas_used {
+            return Ok(gas_used);
+        }
+
+        let gas_used = self
+            .dry_runner
+            .dry_run_and_get_used_gas(tx, tolerance)
+            .await?;
+        self.cache
+            .lock()
+            .expect("poisoned lock")
+            .insert(key, gas_used);
+
+        Ok(gas_used)
+    }
+
+    // The outcome depends on the state of the chain so it is never cached
+    async fn dry_run_and_get_used_gas_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+        tolerance: f32,
+    ) -> Result<u64> {
+        self.dry_runner
+            .dry_run_and_get_used_gas_with_utxo_validation(tx, tolerance)
+            .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<D: DetailedDryRunner> DetailedDryRunner for CachedDryRunner<D> {
+    async fn dry_run_and_get_receipts(&self, tx: FuelTransaction) -> Result<Vec<Receipt>> {
+        self.dry_runner.dry_run_and_get_receipts(tx).await
+    }
+
+    async fn dry_run_and_get_receipts_with_utxo_validation(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<Receipt>> {
+        self.dry_runner
+            .dry_run_and_get_receipts_with_utxo_validation(tx)
+            .await
+    }
+
+    async fn dry_run_and_get_storage_changes(
+        &self,
+        tx: FuelTransaction,
+    ) -> Result<Vec<StorageChange>> {
+        self.dry_runner.dry_run_and_get_storage_changes(tx).await
+    }
+}
+
+/// Raw output of an external signer: a 64-byte signature and its recovery id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawSignature {
+    pub bytes: [u8; 64],
+    pub recovery_id: u8,
+}
+
+/// Signer producing signatures outside of the SDK, e.g. WebAuthn/passkey
+/// authenticators. Signatures are only accepted if they recover to `address`.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ExternalSigner: Debug + Send + Sync {
+    fn address(&self) -> &Bech32Address;
+
+    /// Signs the raw 32-byte `message`, i.e. the transaction id.
+    async fn sign_raw(&self, message: [u8; 32]) -> Result<RawSignature>;
+
+    /// Converts the raw signature into a compact `Signature`. Override this for
+    /// signers that do not natively produce secp256k1 signatures.
+    fn to_signature(&self, raw: RawSignature) -> Result<Signature> {
+        if raw.recovery_id > 1 {
+            return Err(error!(
+                InvalidData,
+                "recovery id must be `0` or `1`, got: `{}`", raw.recovery_id
+            ));
+        }
+
+        // The recovery id is encoded in the most significant bit of `s`
+        let mut bytes = raw.bytes;
+        bytes[32] = (raw.recovery_id << 7) | (bytes[32] & 0x7f);
+
+        Ok(Signature::from_bytes(bytes))
+    }
+}
+
+/// Computes the fee of a transaction, e.g. to model an alternate fee schedule.
+/// Only used for the local fee estimations, the node charges its own fee.
+pub trait FeeCalculator: Debug + Send + Sync {
+    /// Returns `None` if the fee can not be computed, e.g. because it overflows.
+    fn fee(
+        &self,
+        tx: &FuelTransaction,
+        gas_costs: &GasCosts,
+        fee_params: &FeeParameters,
+    ) -> Option<TransactionFee>;
+}
+
+/// The fee model of the chain, i.e. `TransactionFee::checked_from_tx`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFeeCalculator;
+
+impl FeeCalculator for DefaultFeeCalculator {
+    fn fee(
+        &self,
+        tx: &FuelTransaction,
+        gas_costs: &GasCosts,
+        fee_params: &FeeParameters,
+    ) -> Option<TransactionFee> {
+        match tx {
+            FuelTransaction::Script(tx) => {
+                TransactionFee::checked_from_tx(gas_costs, fee_params, tx)
+            }
+            FuelTransaction::Create(tx) => {
+                TransactionFee::checked_from_tx(gas_costs, fee_params, tx)
+            }
+            FuelTransaction::Mint(_) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -77,6 +477,103 @@ struct UnresolvedSignatures {
     #[zeroize(skip)]
     addr_idx_offset_map: HashMap<Bech32Address, u64>,
     secret_keys: Vec<SecretKey>,
+    // Signed after all `secret_keys`, in the order they were added
+    #[zeroize(skip)]
+    external_signers: Vec<Arc<dyn ExternalSigner>>,
+}
+
+impl UnresolvedSignatures {
+    fn len(&self) -> usize {
+        self.secret_keys.len() + self.external_signers.len()
+    }
+
+    fn witness_idx_offset(&self, owner: &Bech32Address) -> Option<u64> {
+        self.addr_idx_offset_map.get(owner).copied().or_else(|| {
+            self.external_signers
+                .iter()
+                .position(|signer| signer.address() == owner)
+                .map(|position| (self.secret_keys.len() + position) as u64)
+        })
+    }
+
+    fn owners_with_idx_offset(&self) -> impl Iterator<Item = (&Bech32Address, u64)> {
+        let external_owners = self
+            .external_signers
+            .iter()
+            .enumerate()
+            .map(|(position, signer)| {
+                (signer.address(), (self.secret_keys.len() + position) as u64)
+            });
+
+        self.addr_idx_offset_map
+            .iter()
+            .map(|(owner, offset)| (owner, *offset))
+            .chain(external_owners)
+    }
+}
+
+/// A problem found while validating a `TransactionBuilder`.
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    #[error("signature missing for input at index `{input_idx}` owned by `{owner}`")]
+    MissingSignature {
+        input_idx: usize,
+        owner: Bech32Address,
+    },
+    #[error("inputs provide `{available}` of asset `{asset_id}` but outputs require `{required}`")]
+    InsufficientBalance {
+        asset_id: AssetId,
+        available: u64,
+        required: u64,
+    },
+    #[error("tx can not have more than 256 witnesses, got: `{actual}`")]
+    TooManyWitnesses { actual: usize },
+    #[error("tx can not have more than `{max}` outputs, got: `{actual}`")]
+    TooManyOutputs { max: usize, actual: usize },
+    #[error("change outputs at indexes `{first_idx}` and `{second_idx}` send asset `{asset_id}` to different recipients: `{first_to}` and `{second_to}`")]
+    ConflictingChangeOutputs {
+        asset_id: AssetId,
+        first_idx: usize,
+        second_idx: usize,
+        first_to: Address,
+        second_to: Address,
+    },
+    #[error("contract output at index `{output_idx}` references input `{input_idx}` but the tx only has `{num_inputs}` inputs")]
+    ContractOutputInputOutOfRange {
+        output_idx: usize,
+        input_idx: u8,
+        num_inputs: usize,
+    },
+    #[error("resource `{id:?}` is spent both by the signed input at index `{signed_idx}` and the predicate input at index `{predicate_idx}`")]
+    SignedAndPredicateResource {
+        id: CoinTypeId,
+        signed_idx: usize,
+        predicate_idx: usize,
+    },
+    #[error("gas price `{gas_price}` is lower than the network minimum of `{min_gas_price}`")]
+    GasPriceBelowMinimum { gas_price: u64, min_gas_price: u64 },
+    /// The coin maturity is compared against the maturity policy of the tx, not the
+    /// current block height. A tx whose maturity is not reached yet is rejected by
+    /// the node as well, so raising the tx maturity only delays the inclusion.
+    #[error("coin at input index `{input_idx}` matures at `{coin_maturity}` but the tx maturity is `{tx_maturity}`")]
+    ImmatureCoin {
+        input_idx: usize,
+        coin_maturity: u32,
+        tx_maturity: u32,
+    },
+    #[error("required signer `{owner}` has no registered signature")]
+    MissingRequiredSigner { owner: Bech32Address },
+    #[error("signer `{owner}` is not in the required signers")]
+    UnexpectedSigner { owner: Bech32Address },
+    #[error("predicate input at index `{input_idx}` spends asset `{asset_id}` which is neither the base asset nor in the outputs")]
+    UninvolvedPredicateAsset { input_idx: usize, asset_id: AssetId },
+    #[error("`bytecode_witness_index` is `{index}` but the tx only has `{num_witnesses}` witnesses, was the contract binary added?")]
+    BytecodeWitnessOutOfRange { index: u8, num_witnesses: usize },
+    #[error("witness at `bytecode_witness_index` `{index}` is empty")]
+    EmptyBytecodeWitness { index: u8 },
+    /// The builder passed validation but building failed, e.g. during the dry run.
+    #[error("{0}")]
+    Build(crate::types::errors::Error),
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -86,6 +583,17 @@ pub trait BuildableTransaction {
     /// Build a `Transaction` from the `TransactionBuilder`. `DryRunner` is
     /// used to return the actual `gas_used` which is set as the `script_gas_limit`.
     async fn build(self, provider: impl DryRunner) -> Result<Self::TxType>;
+
+    /// Build a `Transaction` without a `DryRunner`, e.g. in air-gapped environments.
+    /// Every predicate input must provide its `predicate_gas_used`.
+    fn build_offline(self) -> Result<Self::TxType>;
+
+    /// Like `build` but runs every validation first and returns all the problems
+    /// found instead of stopping at the first one.
+    async fn try_build(
+        self,
+        provider: impl DryRunner,
+    ) -> std::result::Result<Self::TxType, Vec<BuildError>>;
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -95,6 +603,24 @@ impl BuildableTransaction for ScriptTransactionBuilder {
     async fn build(self, provider: impl DryRunner) -> Result<Self::TxType> {
         self.build(provider).await
     }
+
+    fn build_offline(self) -> Result<Self::TxType> {
+        self.build_offline()
+    }
+
+    async fn try_build(
+        self,
+        provider: impl DryRunner,
+    ) -> std::result::Result<Self::TxType, Vec<BuildError>> {
+        let errors = self.build_errors();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.build(provider)
+            .await
+            .map_err(|err| vec![BuildError::Build(err)])
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -106,6 +632,23 @@ impl BuildableTransaction for CreateTransactionBuilder {
     async fn build(self, _: impl DryRunner) -> Result<Self::TxType> {
         self.build()
     }
+
+    fn build_offline(self) -> Result<Self::TxType> {
+        self.build_offline()
+    }
+
+    async fn try_build(
+        self,
+        _: impl DryRunner,
+    ) -> std::result::Result<Self::TxType, Vec<BuildError>> {
+        let mut errors = self.build_errors();
+        errors.extend(self.validate_bytecode_witness().err());
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.build().map_err(|err| vec![BuildError::Build(err)])
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -113,23 +656,179 @@ pub trait TransactionBuilder: BuildableTransaction + Send + Clone {
     type TxType: Transaction;
 
     fn add_unresolved_signature(&mut self, owner: Bech32Address, secret_key: SecretKey);
+    /// Zeroizes and removes all secret keys added with `add_unresolved_signature`.
+    fn clear_signatures(&mut self);
+    /// Removes the inputs, outputs, witnesses and signatures, zeroizing the secret
+    /// keys, so that the builder and its policies can be reused as a template.
+    fn reset_transaction_data(&mut self);
+    /// Returns the witness index the signature of `owner` will have in the built transaction.
+    fn witness_index_for(&self, owner: &Bech32Address) -> Option<u8>;
+    /// Returns the `(witness_index, owner)` pairs of every signer, sorted by index.
+    fn signer_map(&self) -> Vec<(u8, Address)>;
+    /// Whether `build` will dry run the transaction to estimate its gas, i.e. the
+    /// `DryRunner` has to be backed by a node. Any other build can use a no-op runner.
+    fn requires_dry_run(&self) -> bool;
+    /// Lowest gas price the node accepts, used when no gas price is set. Currently
+    /// the network `min_gas_price`.
+    fn minimum_acceptable_gas_price(&self) -> u64;
+    /// Block height from which the transaction can be included, i.e. the value of
+    /// `PolicyType::Maturity` including any raw policy override.
+    fn effective_maturity(&self) -> u32;
     async fn fee_checked_from_tx(&self, provider: impl DryRunner)
         -> Result<Option<TransactionFee>>;
+    /// The max fee converted to `target` with the rate given by `rate_provider`,
+    /// rounded up. Meant for display, the fee is always paid in the base asset.
+    async fn fee_in_asset(
+        &self,
+        target: AssetId,
+        rate_provider: &impl RateProvider,
+        provider: impl DryRunner,
+    ) -> Result<u64>;
     fn with_maturity(self, maturity: u32) -> Self;
     fn with_gas_price(self, gas_price: u64) -> Self;
+    /// Same as `with_gas_price` with the price suggested by `oracle`.
+    async fn with_oracle_gas_price(self, oracle: &impl GasPriceOracle) -> Result<Self>;
     fn with_tx_policies(self, tx_policies: TxPolicies) -> Self;
+    /// Sets policies that take precedence over the ones generated by the builder.
+    /// Only the policy types set in `policies` are overridden, per type, including
+    /// the defaults (e.g. the witness limit) and values from other setters.
+    fn with_raw_policies(self, policies: Policies) -> Self;
     fn with_inputs(self, inputs: Vec<Input>) -> Self;
     fn with_outputs(self, outputs: Vec<Output>) -> Self;
+    /// Appends `count` zeroed `Output::Variable`s, e.g. for contract calls forwarding funds.
+    fn with_variable_outputs(self, count: usize) -> Self;
     fn with_witnesses(self, witnesses: Vec<Witness>) -> Self;
+    /// Appends `data` as a witness, e.g. a proof read by a predicate, and returns
+    /// the index it will have in the built transaction. Fails, leaving the builder
+    /// unchanged, if the witness count or the `WitnessLimit` policy would be exceeded
+    /// once the signatures are added.
+    fn add_witness(&mut self, data: Vec<u8>) -> Result<u8>;
+    /// Same as `add_witness` for a proof verified by a predicate. The returned index
+    /// is meant to be passed in the predicate data so that the predicate can read
+    /// the proof with `GTFArgs::WitnessData`.
+    fn add_proof_witness(self, proof: Vec<u8>) -> Result<(Self, u8)>;
+    /// Skips generating the signature witnesses of the secret keys and external
+    /// signers during build. Inputs still reference the witness indexes their
+    /// signatures would have, so the transaction is invalid unless all of them
+    /// are provided with `with_witnesses`.
+    fn with_manual_witnesses(self) -> Self;
+    /// Replaces the consensus `GasCosts` used by the local fee and gas estimations,
+    /// e.g. to model a hypothetical cost schedule. It does not affect the
+    /// transaction sent to the node nor how the node validates it.
+    fn with_gas_costs_override(self, gas_costs: GasCosts) -> Self;
+    /// Fails the build if the serialized transaction, including its witnesses, is
+    /// larger than `bytes`. A soft cap on top of the consensus limits, e.g. to stay
+    /// within what the infrastructure relaying the transaction accepts.
+    fn with_max_size(self, bytes: usize) -> Self;
+    /// Sets the `TxPointer` of every coin input. `fuel-tx` has no transaction level
+    /// pointer, it is kept per coin and locates the transaction that created it, so
+    /// this only makes sense when all coins come from the same transaction. The
+    /// pointer is zeroed when computing the tx id and doesn't affect the signatures.
+    fn with_tx_pointer(self, tx_pointer: TxPointer) -> Self;
+    /// Fails the build unless the registered signers are exactly `signers`, e.g. to
+    /// enforce the quorum of a multi-sig policy.
+    fn with_required_signers(self, signers: Vec<Bech32Address>) -> Self;
+    /// Replaces the `DefaultFeeCalculator` used by the fee estimations.
+    fn with_fee_calculator(self, fee_calculator: impl FeeCalculator + 'static) -> Self;
     fn inputs(&self) -> &Vec<Input>;
     fn inputs_mut(&mut self) -> &mut Vec<Input>;
     fn outputs(&self) -> &Vec<Output>;
     fn outputs_mut(&mut self) -> &mut Vec<Output>;
+    /// Permutes the outputs so that the output at index `i` is the one previously at
+    /// `new_order[i]`. Fails, leaving the outputs unchanged, if `new_order` is not a
+    /// permutation of the output indexes. Contract outputs reference inputs, not
+    /// outputs, so they stay valid.
+    fn reorder_outputs(&mut self, new_order: Vec<usize>) -> Result<()>;
     fn witnesses(&self) -> &Vec<Witness>;
     fn witnesses_mut(&mut self) -> &mut Vec<Witness>;
     fn consensus_parameters(&self) -> &ConsensusParameters;
+    /// Adds a predicate input owned by a sponsor that pays the transaction fee.
+    /// The base asset change is returned to the predicate owner so that the fee
+    /// is drawn from the sponsor and not from the signed inputs. There is only one
+    /// change output per asset, so the sponsor also receives the base asset of the
+    /// signed inputs that isn't spent. Surplus meant for the user has to be sent
+    /// back with a coin output. Fails if the base asset change goes to someone else.
+    fn with_fee_sponsor(
+        self,
+        resource: CoinType,
+        code: Vec<u8>,
+        data: UnresolvedBytes,
+    ) -> Result<Self>;
+    /// Adds `message` as an input spent by the predicate `code` with `data`, e.g. to
+    /// relay a bridged message. Whether the resolved input carries the message data
+    /// depends on `message.data`, see `create_coin_message_predicate`.
+    fn add_message_predicate(self, message: Message, code: Vec<u8>, data: UnresolvedBytes) -> Self;
+    /// Adds `resource` as an input spent by the compiled predicate `code`, with the
+    /// predicate data being the ABI encoding of the predicate arguments `args`.
+    fn add_predicate_with_args(
+        self,
+        resource: CoinType,
+        code: Vec<u8>,
+        args: &[Token],
+    ) -> Result<Self>;
+    /// Adds `resource` as an input spent by the predicate compiled into `artifact`.
+    /// `args` set the configurable constants in the code and provide the `main`
+    /// arguments encoded into the predicate data, see `PredicateArtifact::code_and_data`.
+    fn add_predicate_from_artifact(
+        self,
+        resource: CoinType,
+        artifact: &PredicateArtifact,
+        args: Vec<ConfigurableValue>,
+    ) -> Result<Self>;
+    /// Fee added by including `input` in the transaction, derived from its serialized
+    /// size and signature verification cost. No dry run is performed so the gas used
+    /// by predicate execution is not included.
+    fn marginal_input_fee(&self, input: &Input) -> Result<u64>;
+    /// Fee of the transaction at a hypothetical `gas_price`, assuming the script
+    /// uses `assumed_gas_used`. Computed offline so it can be called repeatedly,
+    /// e.g. to display a fee curve. The gas used by predicates is not included and
+    /// `assumed_gas_used` is ignored for `CreateTransaction`s.
+    fn fee_at_gas_price(&self, gas_price: u64, assumed_gas_used: u64) -> Result<TransactionFee>;
+    /// `fee_at_gas_price` for each of `gas_prices`, in the same order, e.g. to
+    /// display slow/medium/fast fee tiers.
+    fn fee_tiers(&self, gas_prices: &[u64], assumed_gas_used: u64) -> Result<Vec<TransactionFee>>;
+    /// Pulls resources from `stream` as signed inputs until the base asset they hold
+    /// covers the base asset coin outputs plus the fee. The fee is estimated once
+    /// for the current transaction and then increased by the `marginal_input_fee`
+    /// of every pulled input. Coins in `exclude_utxos`, e.g. ones reserved by
+    /// concurrent builds, and resources of other assets are skipped. Fails if the
+    /// owner of a pulled resource has no registered signer.
+    async fn fund_from_stream(
+        self,
+        stream: &mut dyn ResourceStream,
+        exclude_utxos: &HashSet<UtxoId>,
+        provider: impl DryRunner,
+    ) -> Result<Self>;
+    /// Fetches the coin with the given `utxo_id` from `provider` and adds it as a
+    /// signed input, registering `secret_key` as the signer of `owner`. Fails if the
+    /// coin does not exist or is not owned by `owner`.
+    async fn add_coin_by_id(
+        &mut self,
+        utxo_id: UtxoId,
+        owner: Bech32Address,
+        secret_key: SecretKey,
+        provider: &impl ResourceQuerier,
+    ) -> Result<()>;
+    /// Fails if a message input is already spent, either according to its `status`
+    /// or to `provider`, since the node would reject the transaction.
+    async fn validate_messages_spendable(&self, provider: &impl MessageStatusQuerier)
+        -> Result<()>;
+    /// Amount of the base asset the single base asset change output will receive:
+    /// the base asset inputs minus the coin outputs and the fee. The fee assumes
+    /// the whole `script_gas_limit` is consumed, so the actual change can be higher.
+    async fn predicted_change_amount(&self, provider: impl DryRunner) -> Result<u64>;
+    /// Whether the inputs cover the coin outputs of every asset and, for the base
+    /// asset, the estimated fee on top.
+    async fn is_fully_funded(&self, provider: impl DryRunner) -> Result<bool>;
 }
 
+const POLICY_TYPES: [PolicyType; 4] = [
+    PolicyType::GasPrice,
+    PolicyType::WitnessLimit,
+    PolicyType::Maturity,
+    PolicyType::MaxFee,
+];
+
 macro_rules! impl_tx_trait {
     ($ty: ty, $tx_ty: ident) => {
         #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -144,21 +843,90 @@ macro_rules! impl_tx_trait {
                     .insert(owner, index_offset);
             }
 
+            fn clear_signatures(&mut self) {
+                self.unresolved_signatures.zeroize();
+                self.unresolved_signatures.addr_idx_offset_map.clear();
+                self.unresolved_signatures.external_signers.clear();
+            }
+
+            fn reset_transaction_data(&mut self) {
+                self.inputs.clear();
+                self.outputs.clear();
+                self.witnesses.clear();
+                self.clear_signatures();
+            }
+
+            fn witness_index_for(&self, owner: &Bech32Address) -> Option<u8> {
+                self.unresolved_signatures
+                    .witness_idx_offset(owner)
+                    .map(|witness_idx_offset| self.witnesses.len() as u8 + witness_idx_offset as u8)
+            }
+
+            fn requires_dry_run(&self) -> bool {
+                self.requires_dry_run()
+            }
+
+            fn minimum_acceptable_gas_price(&self) -> u64 {
+                self.network_info.min_gas_price
+            }
+
+            fn effective_maturity(&self) -> u32 {
+                self.raw_policies
+                    .as_ref()
+                    .and_then(|policies| policies.get(PolicyType::Maturity))
+                    .map_or(self.maturity, |maturity| maturity as u32)
+            }
+
+            fn signer_map(&self) -> Vec<(u8, Address)> {
+                let num_witnesses = self.witnesses.len() as u8;
+
+                let mut signers: Vec<_> = self
+                    .unresolved_signatures
+                    .owners_with_idx_offset()
+                    .map(|(owner, witness_idx_offset)| {
+                        (num_witnesses + witness_idx_offset as u8, owner.into())
+                    })
+                    .collect();
+                signers.sort_by_key(|(witness_idx, _)| *witness_idx);
+
+                signers
+            }
+
             async fn fee_checked_from_tx(
                 &self,
                 provider: impl DryRunner,
             ) -> Result<Option<TransactionFee>> {
-                let mut tx = BuildableTransaction::build(self.clone(), provider).await?;
+                let mut tx = self.resolve_fee_estimation_tx(provider).await?;
+                self.estimate_tx_predicates(&mut tx)?;
 
-                if tx.is_using_predicates() {
-                    tx.estimate_predicates(self.consensus_parameters())?;
+                Ok(self.calculate_fee(tx.tx.into()))
+            }
+
+            async fn fee_in_asset(
+                &self,
+                target: AssetId,
+                rate_provider: &impl RateProvider,
+                provider: impl DryRunner,
+            ) -> Result<u64> {
+                let fee = self
+                    .fee_checked_from_tx(provider)
+                    .await?
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?
+                    .max_fee();
+
+                if target == BASE_ASSET_ID {
+                    return Ok(fee);
                 }
 
-                Ok(TransactionFee::checked_from_tx(
-                    &self.consensus_parameters().gas_costs,
-                    &self.consensus_parameters().fee_params,
-                    &tx.tx,
-                ))
+                let rate = rate_provider.rate(BASE_ASSET_ID, target).await?;
+                if !rate.is_finite() || rate < 0.0 {
+                    return Err(error!(
+                        InvalidData,
+                        "rate from the base asset to `{target}` must be a non-negative number, got: `{rate}`"
+                    ));
+                }
+
+                Ok((fee as f64 * rate).ceil() as u64)
             }
 
             fn with_maturity(mut self, maturity: u32) -> Self {
@@ -171,10 +939,21 @@ macro_rules! impl_tx_trait {
                 self
             }
 
+            async fn with_oracle_gas_price(self, oracle: &impl GasPriceOracle) -> Result<Self> {
+                let gas_price = oracle.suggested_gas_price().await?;
+
+                Ok(self.with_gas_price(gas_price))
+            }
+
             fn with_tx_policies(self, tx_policies: TxPolicies) -> Self {
                 self.with_tx_policies(tx_policies)
             }
 
+            fn with_raw_policies(mut self, policies: Policies) -> Self {
+                self.raw_policies = Some(policies);
+                self
+            }
+
             fn with_inputs(mut self, inputs: Vec<Input>) -> Self {
                 self.inputs = inputs;
                 self
@@ -185,11 +964,89 @@ macro_rules! impl_tx_trait {
                 self
             }
 
+            fn with_variable_outputs(mut self, count: usize) -> Self {
+                self.outputs.extend(
+                    repeat_with(|| Output::variable(Default::default(), 0, Default::default()))
+                        .take(count),
+                );
+                self
+            }
+
             fn with_witnesses(mut self, witnesses: Vec<Witness>) -> Self {
                 self.witnesses = witnesses;
                 self
             }
 
+            fn add_witness(&mut self, data: Vec<u8>) -> Result<u8> {
+                self.witnesses.push(Witness::from(data));
+
+                let witnesses_size: usize = self
+                    .fee_estimation_witnesses()
+                    .iter()
+                    .map(|witness| witness.size())
+                    .sum();
+                let witness_limit = self
+                    .generate_fuel_policies()
+                    .get(PolicyType::WitnessLimit)
+                    .unwrap_or(u64::MAX);
+
+                let validation = self
+                    .validate_witnesses_count()
+                    .map_err(|err| error!(InvalidData, "{err}"))
+                    .and_then(|_| {
+                        if witnesses_size as u64 > witness_limit {
+                            return Err(error!(
+                                InvalidData,
+                                "witnesses take `{witnesses_size}` bytes, exceeding the witness limit of `{witness_limit}`"
+                            ));
+                        }
+
+                        Ok(())
+                    });
+                if let Err(err) = validation {
+                    self.witnesses.pop();
+                    return Err(err);
+                }
+
+                Ok((self.witnesses.len() - 1) as u8)
+            }
+
+            fn add_proof_witness(mut self, proof: Vec<u8>) -> Result<(Self, u8)> {
+                let witness_idx = self.add_witness(proof)?;
+
+                Ok((self, witness_idx))
+            }
+
+            fn with_manual_witnesses(mut self) -> Self {
+                self.manual_witnesses = true;
+                self
+            }
+
+            fn with_gas_costs_override(mut self, gas_costs: GasCosts) -> Self {
+                self.gas_costs_override = Some(gas_costs);
+                self
+            }
+
+            fn with_max_size(mut self, bytes: usize) -> Self {
+                self.max_size = Some(bytes);
+                self
+            }
+
+            fn with_tx_pointer(mut self, tx_pointer: TxPointer) -> Self {
+                self.tx_pointer = tx_pointer;
+                self
+            }
+
+            fn with_required_signers(mut self, signers: Vec<Bech32Address>) -> Self {
+                self.required_signers = Some(signers);
+                self
+            }
+
+            fn with_fee_calculator(mut self, fee_calculator: impl FeeCalculator + 'static) -> Self {
+                self.fee_calculator = Arc::new(fee_calculator);
+                self
+            }
+
             fn inputs(&self) -> &Vec<Input> {
                 self.inputs.as_ref()
             }
@@ -206,6 +1063,23 @@ macro_rules! impl_tx_trait {
                 &mut self.outputs
             }
 
+            fn reorder_outputs(&mut self, new_order: Vec<usize>) -> Result<()> {
+                let num_outputs = self.outputs.len();
+                let is_permutation = new_order.len() == num_outputs
+                    && new_order.iter().all(|idx| *idx < num_outputs)
+                    && new_order.iter().all_unique();
+                if !is_permutation {
+                    return Err(error!(
+                        InvalidData,
+                        "`{new_order:?}` is not a permutation of the `{num_outputs}` output indexes"
+                    ));
+                }
+
+                self.outputs = new_order.into_iter().map(|idx| self.outputs[idx]).collect();
+
+                Ok(())
+            }
+
             fn witnesses(&self) -> &Vec<Witness> {
                 self.witnesses.as_ref()
             }
@@ -217,714 +1091,6740 @@ macro_rules! impl_tx_trait {
             fn consensus_parameters(&self) -> &ConsensusParameters {
                 &self.network_info.consensus_parameters
             }
-        }
 
-        impl $ty {
-            fn generate_shared_fuel_policies(&self) -> Policies {
-                let mut policies = Policies::default();
+            fn with_fee_sponsor(
+                mut self,
+                resource: CoinType,
+                code: Vec<u8>,
+                data: UnresolvedBytes,
+            ) -> Result<Self> {
+                if resource.asset_id() != BASE_ASSET_ID {
+                    return Err(error!(
+                        InvalidData,
+                        "fee sponsor must provide the base asset, got: `{}`",
+                        resource.asset_id()
+                    ));
+                }
 
-                policies.set(PolicyType::MaxFee, self.max_fee);
-                policies.set(PolicyType::Maturity, Some(self.maturity as u64));
+                let sponsor: Address = resource.owner().into();
+                let base_change_owner = self.outputs.iter().find_map(|output| match output {
+                    Output::Change { to, asset_id, .. } if *asset_id == BASE_ASSET_ID => Some(*to),
+                    _ => None,
+                });
 
-                policies.set(
-                    PolicyType::GasPrice,
-                    self.gas_price.or(Some(self.network_info.min_gas_price)),
-                );
+                match base_change_owner {
+                    Some(owner) if owner != sponsor => {
+                        return Err(error!(
+                            InvalidData,
+                            "base asset change goes to `{owner}` but must be returned to the fee sponsor `{sponsor}`"
+                        ));
+                    }
+                    Some(_) => {}
+                    None => self
+                        .outputs
+                        .push(Output::change(sponsor, 0, BASE_ASSET_ID)),
+                }
 
-                policies
-            }
+                self.inputs
+                    .push(Input::resource_predicate(resource, code, data));
 
-            fn is_using_predicates(&self) -> bool {
-                self.inputs()
-                    .iter()
-                    .any(|input| matches!(input, Input::ResourcePredicate { .. }))
+                Ok(self)
+            }
+
+            fn add_message_predicate(
+                mut self,
+                message: Message,
+                code: Vec<u8>,
+                data: UnresolvedBytes,
+            ) -> Self {
+                self.inputs.push(Input::resource_predicate(
+                    CoinType::Message(message),
+                    code,
+                    data,
+                ));
+                self
+            }
+
+            fn add_predicate_with_args(
+                mut self,
+                resource: CoinType,
+                code: Vec<u8>,
+                args: &[Token],
+            ) -> Result<Self> {
+                let data = ABIEncoder::encode(args)?;
+                self.inputs
+                    .push(Input::resource_predicate(resource, code, data));
+
+                Ok(self)
+            }
+
+            fn add_predicate_from_artifact(
+                mut self,
+                resource: CoinType,
+                artifact: &PredicateArtifact,
+                args: Vec<ConfigurableValue>,
+            ) -> Result<Self> {
+                let (code, data) = artifact.code_and_data(args)?;
+                self.inputs
+                    .push(Input::resource_predicate(resource, code, data));
+
+                Ok(self)
+            }
+
+            async fn fund_from_stream(
+                mut self,
+                stream: &mut dyn ResourceStream,
+                exclude_utxos: &HashSet<UtxoId>,
+                provider: impl DryRunner,
+            ) -> Result<Self> {
+                let fee = self
+                    .fee_checked_from_tx(provider)
+                    .await?
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?;
+
+                let mut available = self.base_asset_input_amount()?;
+                let mut required =
+                    checked_sum([self.base_asset_coin_output_amount()?, fee.max_fee()])?;
+
+                while available < required {
+                    let resource = stream.next_resource().await?.ok_or(error!(
+                        InvalidData,
+                        "resource stream exhausted, covered `{available}` of the required `{required}` base asset"
+                    ))?;
+
+                    let is_excluded = match resource.id() {
+                        CoinTypeId::UtxoId(utxo_id) => exclude_utxos.contains(&utxo_id),
+                        CoinTypeId::Nonce(_) => false,
+                    };
+                    // Other assets would only make the transaction more expensive
+                    if is_excluded || resource.asset_id() != BASE_ASSET_ID {
+                        continue;
+                    }
+
+                    let owner = resource.owner();
+                    if self.unresolved_signatures.witness_idx_offset(owner).is_none() {
+                        return Err(error!(
+                            InvalidData,
+                            "signature missing for resource with owner: `{owner:?}`"
+                        ));
+                    }
+
+                    available = checked_sum([available, resource.amount()])?;
+                    let input = Input::resource_signed(resource);
+                    required = checked_sum([required, self.marginal_input_fee(&input)?])?;
+
+                    self.inputs.push(input);
+                }
+
+                Ok(self)
+            }
+
+            async fn add_coin_by_id(
+                &mut self,
+                utxo_id: UtxoId,
+                owner: Bech32Address,
+                secret_key: SecretKey,
+                provider: &impl ResourceQuerier,
+            ) -> Result<()> {
+                let coin = provider
+                    .coin(&utxo_id)
+                    .await?
+                    .ok_or(error!(InvalidData, "coin `{utxo_id}` not found"))?;
+
+                if coin.owner != owner {
+                    return Err(error!(
+                        InvalidData,
+                        "coin `{utxo_id}` is owned by `{}`, not by `{owner}`",
+                        coin.owner
+                    ));
+                }
+
+                if self.witness_index_for(&owner).is_none() {
+                    self.add_unresolved_signature(owner, secret_key);
+                }
+                self.inputs
+                    .push(Input::resource_signed(CoinType::Coin(coin)));
+
+                Ok(())
+            }
+
+            async fn validate_messages_spendable(
+                &self,
+                provider: &impl MessageStatusQuerier,
+            ) -> Result<()> {
+                for (input_idx, input) in self.inputs.iter().enumerate() {
+                    let message = match input {
+                        Input::ResourceSigned {
+                            resource: CoinType::Message(message),
+                        }
+                        | Input::ResourcePredicate {
+                            resource: CoinType::Message(message),
+                            ..
+                        } => message,
+                        _ => continue,
+                    };
+
+                    let is_spent = message.status == MessageStatus::Spent
+                        || provider.message_status(message).await? == MessageStatus::Spent;
+                    if is_spent {
+                        return Err(error!(
+                            InvalidData,
+                            "message input at index `{input_idx}` with nonce `{}` is already spent",
+                            message.nonce
+                        ));
+                    }
+                }
+
+                Ok(())
+            }
+
+            async fn is_fully_funded(&self, provider: impl DryRunner) -> Result<bool> {
+                if !self.insufficient_balances().is_empty() {
+                    return Ok(false);
+                }
+
+                let max_fee = self
+                    .fee_checked_from_tx(provider)
+                    .await?
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?
+                    .max_fee();
+                let required = checked_sum([self.base_asset_coin_output_amount()?, max_fee])?;
+
+                Ok(self.base_asset_input_amount()? >= required)
+            }
+
+            async fn predicted_change_amount(&self, provider: impl DryRunner) -> Result<u64> {
+                let num_base_change_outputs = self
+                    .outputs
+                    .iter()
+                    .filter(|output| {
+                        matches!(output, Output::Change { asset_id, .. } if *asset_id == BASE_ASSET_ID)
+                    })
+                    .count();
+                if num_base_change_outputs != 1 {
+                    return Err(error!(
+                        InvalidData,
+                        "expected exactly one base asset change output, got: `{num_base_change_outputs}`"
+                    ));
+                }
+
+                let fee = self
+                    .fee_checked_from_tx(provider)
+                    .await?
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?;
+
+                self.base_asset_input_amount()?
+                    .checked_sub(self.base_asset_coin_output_amount()?)
+                    .and_then(|amount| amount.checked_sub(fee.max_fee()))
+                    .ok_or(error!(
+                        InvalidData,
+                        "base asset inputs do not cover the coin outputs and the fee"
+                    ))
+            }
+
+            fn fee_at_gas_price(
+                &self,
+                gas_price: u64,
+                assumed_gas_used: u64,
+            ) -> Result<TransactionFee> {
+                let tx = self
+                    .clone()
+                    .with_gas_price(gas_price)
+                    .resolve_fuel_tx_for_fee(assumed_gas_used)?;
+
+                self.calculate_fee(tx.into())
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))
+            }
+
+            fn fee_tiers(
+                &self,
+                gas_prices: &[u64],
+                assumed_gas_used: u64,
+            ) -> Result<Vec<TransactionFee>> {
+                gas_prices
+                    .iter()
+                    .map(|gas_price| self.fee_at_gas_price(*gas_price, assumed_gas_used))
+                    .collect()
+            }
+
+            fn marginal_input_fee(&self, input: &Input) -> Result<u64> {
+                let consensus_parameters = self.consensus_parameters();
+                let gas_costs = self.estimation_gas_costs();
+
+                let (fuel_input, verification_gas) = match input.clone() {
+                    Input::ResourceSigned { resource } => {
+                        let is_new_signer = self
+                            .unresolved_signatures
+                            .witness_idx_offset(resource.owner())
+                            .is_none();
+                        let fuel_input = match resource {
+                            CoinType::Coin(coin) => {
+                                create_coin_input_with_tx_pointer(coin, 0, self.tx_pointer)
+                            }
+                            CoinType::Message(message) => create_coin_message_input(message, 0),
+                        };
+
+                        // Only a new signer adds a signature that has to be recovered
+                        (fuel_input, if is_new_signer { gas_costs.ecr1 } else { 0 })
+                    }
+                    Input::ResourcePredicate {
+                        resource,
+                        code,
+                        data,
+                        predicate_gas_used,
+                    } => {
+                        let code_len = code.len() as u64;
+                        let (fuel_input, _) = resolve_predicate_resource(
+                            resource,
+                            code,
+                            data,
+                            predicate_gas_used,
+                            &mut 0,
+                            self.tx_pointer,
+                        )?;
+
+                        // The predicate owner is checked against the root of the predicate code
+                        (fuel_input, gas_costs.contract_root.resolve(code_len))
+                    }
+                    Input::Contract {
+                        utxo_id,
+                        balance_root,
+                        state_root,
+                        tx_pointer,
+                        contract_id,
+                    } => (
+                        FuelInput::contract(utxo_id, balance_root, state_root, tx_pointer, contract_id),
+                        0,
+                    ),
+                };
+
+                let gas = (fuel_input.size() as u64)
+                    .checked_mul(consensus_parameters.fee_params.gas_per_byte)
+                    .and_then(|bytes_gas| bytes_gas.checked_add(verification_gas))
+                    .ok_or(error!(InvalidData, "gas used by the input overflows `u64`"))?;
+
+                gas_to_fee(gas, self.effective_gas_price(), &consensus_parameters.fee_params)
+                    .ok_or(error!(InvalidData, "fee of the input overflows `u64`"))
+            }
+        }
+
+        impl $ty {
+            /// Adds a synthetic signed coin of `asset_id` owned by `secret_key`, holding half
+            /// of `u64::MAX` so that it covers any fee, and registers its signature. Saves
+            /// tests from building coins only to fund the transaction. Only available with
+            /// the `test-helpers` feature, which the example needs as well. Fails if the
+            /// index of the new input does not fit in the `u8` of its `UtxoId`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use fuel_crypto::SecretKey;
+            /// use fuels_core::{
+            ///     constants::BASE_ASSET_ID,
+            ///     types::{
+            ///         transaction::Transaction,
+            ///         transaction_builders::{NetworkInfo, ScriptTransactionBuilder},
+            ///     },
+            /// };
+            ///
+            /// let network_info = NetworkInfo {
+            ///     consensus_parameters: Default::default(),
+            ///     min_gas_price: 0,
+            /// };
+            /// let secret_key: SecretKey = format!("{:064x}", 1).parse().unwrap();
+            ///
+            /// let tx = ScriptTransactionBuilder::new(network_info)
+            ///     .with_auto_funded_input(secret_key, BASE_ASSET_ID)
+            ///     .unwrap()
+            ///     .build_offline()
+            ///     .unwrap();
+            ///
+            /// assert_eq!(tx.inputs().len(), 1);
+            /// assert_eq!(tx.witnesses().len(), 1);
+            /// ```
+            #[cfg(any(test, feature = "test-helpers"))]
+            pub fn with_auto_funded_input(
+                mut self,
+                secret_key: SecretKey,
+                asset_id: AssetId,
+            ) -> Result<Self> {
+                let output_index = u8::try_from(self.inputs.len()).map_err(|_| {
+                    error!(
+                        InvalidData,
+                        "auto funded input can not be added after `{}` inputs",
+                        self.inputs.len()
+                    )
+                })?;
+                let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+                let coin = Coin {
+                    amount: u64::MAX / 2,
+                    asset_id,
+                    utxo_id: UtxoId::new(Bytes32::new([u8::MAX; 32]), output_index),
+                    owner: owner.clone(),
+                    ..Default::default()
+                };
+                self.inputs.push(Input::resource_signed(CoinType::Coin(coin)));
+
+                if self.unresolved_signatures.witness_idx_offset(&owner).is_none() {
+                    self.add_unresolved_signature(owner, secret_key);
+                }
+
+                Ok(self)
+            }
+
+            fn generate_shared_fuel_policies(&self) -> Policies {
+                let mut policies = Policies::default();
+
+                policies.set(PolicyType::MaxFee, self.max_fee);
+                policies.set(PolicyType::Maturity, Some(self.maturity as u64));
+
+                policies.set(PolicyType::GasPrice, Some(self.effective_gas_price()));
+
+                policies
+            }
+
+            fn apply_raw_policies(&self, policies: &mut Policies) {
+                let Some(raw_policies) = &self.raw_policies else {
+                    return;
+                };
+
+                for policy_type in POLICY_TYPES {
+                    if let Some(value) = raw_policies.get(policy_type) {
+                        policies.set(policy_type, Some(value));
+                    }
+                }
+            }
+
+            /// Returns the signature witness every secret key will produce, paired with its
+            /// owner and sorted by witness index. The witnesses are identical to the ones
+            /// added during build. Fails if external signers were added since their
+            /// signatures can only be produced during build.
+            pub fn preview_signature_witnesses(
+                &self,
+                chain_id: ChainId,
+            ) -> Result<Vec<(Bech32Address, Witness)>> {
+                if !self.unresolved_signatures.external_signers.is_empty() {
+                    return Err(error!(
+                        InvalidData,
+                        "signatures of external signers can not be previewed"
+                    ));
+                }
+
+                let witnesses = generate_missing_witnesses(
+                    self.preview_tx_id(&chain_id)?,
+                    &self.unresolved_signatures,
+                );
+
+                let mut owners: Vec<_> = self
+                    .unresolved_signatures
+                    .addr_idx_offset_map
+                    .iter()
+                    .collect();
+                owners.sort_by_key(|(_, witness_idx_offset)| **witness_idx_offset);
+
+                Ok(owners
+                    .into_iter()
+                    .map(|(owner, witness_idx_offset)| {
+                        (owner.clone(), witnesses[*witness_idx_offset as usize].clone())
+                    })
+                    .collect())
+            }
+
+            /// Absolute offset of the data of every predicate input, in input order. The
+            /// first one is seeded by the offset of the inputs and every input, predicate
+            /// or not, moves the following ones.
+            pub fn predicate_offsets(&self) -> Result<Vec<usize>> {
+                Ok(self
+                    .resolved_predicate_data()?
+                    .into_iter()
+                    .map(|(offset, _)| offset)
+                    .collect())
+            }
+
+            /// Returns, for every predicate input, the absolute offset of its data and the
+            /// resolved data bytes, i.e. exactly what the predicate will read.
+            pub fn resolved_predicate_data(&self) -> Result<Vec<(usize, Vec<u8>)>> {
+                let resolved_inputs = resolve_fuel_inputs_with_predicate_data_offsets(
+                    self.inputs.clone(),
+                    self.inputs_offset(),
+                    self.num_witnesses()?,
+                    &self.unresolved_signatures,
+                    self.tx_pointer,
+                )?;
+
+                Ok(resolved_inputs
+                    .into_iter()
+                    .filter_map(|(input, predicate_data_offset)| {
+                        let predicate_data = input.input_predicate_data()?.to_vec();
+                        Some((predicate_data_offset?, predicate_data))
+                    })
+                    .collect())
+            }
+
+            /// Everything needed to present the transaction for approval: the amounts
+            /// spent and sent, the fee, the gas limit, the policies and the signers.
+            /// Dry runs the transaction if its `script_gas_limit` has to be estimated.
+            pub async fn summary(&self, provider: impl DryRunner) -> Result<TransactionSummary> {
+                let mut tx = self.resolve_fee_estimation_tx(provider).await?;
+                self.estimate_tx_predicates(&mut tx)?;
+                let fee = self
+                    .calculate_fee(tx.tx.clone().into())
+                    .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?;
+
+                let mut input_amounts: BTreeMap<AssetId, u64> = BTreeMap::new();
+                for input in &self.inputs {
+                    if let (Some(asset_id), Some(amount)) = (input.asset_id(), input.amount()) {
+                        let total = input_amounts.entry(asset_id).or_default();
+                        *total = checked_sum([*total, amount])?;
+                    }
+                }
+
+                let mut outputs = vec![];
+                let mut change_recipients = BTreeMap::new();
+                for output in &self.outputs {
+                    match output {
+                        Output::Coin {
+                            to,
+                            amount,
+                            asset_id,
+                        } => outputs.push(OutputSummary {
+                            to: *to,
+                            asset_id: *asset_id,
+                            amount: *amount,
+                        }),
+                        Output::Change { to, asset_id, .. } => {
+                            change_recipients.entry(*asset_id).or_insert(*to);
+                        }
+                        _ => {}
+                    }
+                }
+
+                Ok(TransactionSummary {
+                    input_amounts,
+                    outputs,
+                    change_recipients,
+                    min_fee: fee.min_fee(),
+                    max_fee: fee.max_fee(),
+                    max_gas: tx.max_gas(self.consensus_parameters()),
+                    policies: tx.tx.policies().clone(),
+                    signers: self
+                        .signer_map()
+                        .into_iter()
+                        .map(|(_, signer)| signer)
+                        .collect(),
+                })
+            }
+
+            /// Serializes the inputs, outputs and witnesses to JSON. Predicate data is
+            /// kept unresolved and signing keys are never included.
+            pub fn to_json(&self) -> Result<String> {
+                let components = TransactionComponents {
+                    inputs: self.inputs.clone(),
+                    outputs: self.outputs.clone(),
+                    witnesses: self.witnesses.clone(),
+                };
+
+                Ok(serde_json::to_string(&components)?)
+            }
+
+            /// Creates a builder from the inputs, outputs and witnesses described by
+            /// `json`, e.g. the output of `to_json`. Signatures have to be added again.
+            pub fn from_json(json: &str, network_info: NetworkInfo) -> Result<Self> {
+                let components: TransactionComponents = serde_json::from_str(json)?;
+
+                Ok(Self::new(network_info)
+                    .with_inputs(components.inputs)
+                    .with_outputs(components.outputs)
+                    .with_witnesses(components.witnesses))
+            }
+
+            /// Serialized size of the transaction without its witnesses, i.e. what is
+            /// left for them within the size limits. See `witness_sizes`.
+            pub fn size_without_witnesses(&self) -> Result<usize> {
+                let mut tx = self.resolve_fuel_tx_for_fee(0)?;
+                tx.witnesses_mut().clear();
+
+                Ok(tx.size())
+            }
+
+            /// How many more signed coin inputs fit before reaching `max_inputs`,
+            /// `max_witnesses`, the `witness_limit` or the `max_size` set on the builder,
+            /// e.g. to stop coin selection gracefully. Every coin is assumed to be owned
+            /// by a new signer so it also adds a signature witness. Fails if the
+            /// signatures already exceed `max_witnesses`.
+            pub fn remaining_input_capacity(&self) -> Result<usize> {
+                self.validate_witnesses_count()
+                    .map_err(|err| error!(InvalidData, "{err}"))?;
+
+                let tx_params = self.consensus_parameters().tx_params();
+                let by_inputs = (tx_params.max_inputs as usize).saturating_sub(self.inputs.len());
+
+                // Witness indexes are `u8`s
+                let witness_sizes = self.witness_sizes();
+                let max_witnesses = (tx_params.max_witnesses as usize).min(256);
+                let by_witnesses = max_witnesses.saturating_sub(witness_sizes.len());
+
+                let witnesses_size: usize = witness_sizes.iter().sum();
+                let signature_size = Witness::from(vec![0u8; Signature::LEN]).size();
+                let witness_limit = self
+                    .generate_fuel_policies()
+                    .get(PolicyType::WitnessLimit)
+                    .unwrap_or_default() as usize;
+                let by_witness_limit = witness_limit.saturating_sub(witnesses_size) / signature_size;
+
+                let capacity = by_inputs.min(by_witnesses).min(by_witness_limit);
+                let Some(max_size) = self.max_size else {
+                    return Ok(capacity);
+                };
+                let tx_size = self.size_without_witnesses()? + witnesses_size;
+                let input_size = create_coin_input(Coin::default(), 0).size() + signature_size;
+                let by_size = max_size.saturating_sub(tx_size) / input_size;
+
+                Ok(capacity.min(by_size))
+            }
+
+            /// Sets the `witness_limit` to the serialized size of the witnesses the
+            /// built transaction will have, signatures included, so that no fee is
+            /// paid for an unused allowance. The sizes already count the length
+            /// prefix of every witness, like the witness limit check of the node, so
+            /// no margin is needed. Witnesses added afterwards don't fit.
+            pub fn fit_witness_limit(&mut self) -> Result<()> {
+                self.validate_witnesses_count()
+                    .map_err(|err| error!(InvalidData, "{err}"))?;
+
+                let witnesses_size: usize = self.witness_sizes().iter().sum();
+                self.witness_limit = Some(witnesses_size as u64);
+
+                Ok(())
+            }
+
+            /// Serialized size of every witness the built transaction will have,
+            /// including the signatures added during build.
+            pub fn witness_sizes(&self) -> Vec<usize> {
+                self.fee_estimation_witnesses()
+                    .iter()
+                    .map(|witness| witness.size())
+                    .collect()
+            }
+
+            /// Whether any input is a contract, e.g. to warn about a contract interaction
+            /// before signing.
+            pub fn interacts_with_contracts(&self) -> bool {
+                self.inputs
+                    .iter()
+                    .any(|input| matches!(input, Input::Contract { .. }))
+            }
+
+            /// Returns the id of every contract input, in order. These are the contracts
+            /// the transaction interacts with and that need matching contract outputs.
+            pub fn called_contract_ids(&self) -> Vec<ContractId> {
+                self.inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        Input::Contract { contract_id, .. } => Some(*contract_id),
+                        _ => None,
+                    })
+                    .collect()
+            }
+
+            /// Bytes taken by predicate code repeated across inputs. The code is stored
+            /// inline in every predicate input and cannot be referenced, so inputs of
+            /// the same predicate each add the full (padded) code to the tx size and fee.
+            pub fn duplicated_predicate_code_size(&self) -> usize {
+                let mut seen_codes = HashSet::new();
+
+                self.inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        Input::ResourcePredicate { code, .. } => Some(code),
+                        _ => None,
+                    })
+                    .filter(|code| !seen_codes.insert(*code))
+                    .map(|code| padded_len_usize(code.len()))
+                    .sum()
+            }
+
+            /// Compares the inputs, outputs, witnesses and resulting policies with the
+            /// ones of `other`, e.g. to inspect the changes made by a transformation.
+            /// Elements are matched by value regardless of their position.
+            pub fn diff(&self, other: &Self) -> BuilderDiff {
+                let (added_inputs, removed_inputs) = diff_items(&self.inputs, &other.inputs);
+                let (added_outputs, removed_outputs) = diff_items(&self.outputs, &other.outputs);
+                let (added_witnesses, removed_witnesses) =
+                    diff_items(&self.witnesses, &other.witnesses);
+
+                let policies = self.generate_fuel_policies();
+                let other_policies = other.generate_fuel_policies();
+                let changed_policies = POLICY_TYPES
+                    .into_iter()
+                    .map(|policy_type| {
+                        (
+                            policy_type,
+                            policies.get(policy_type),
+                            other_policies.get(policy_type),
+                        )
+                    })
+                    .filter(|(_, old, new)| old != new)
+                    .collect();
+
+                BuilderDiff {
+                    added_inputs,
+                    removed_inputs,
+                    added_outputs,
+                    removed_outputs,
+                    added_witnesses,
+                    removed_witnesses,
+                    changed_policies,
+                }
+            }
+
+            /// Returns the policies that will be set on the built transaction, including
+            /// the witness limit default and the raw policy overrides.
+            pub fn resolved_policies(&self) -> Policies {
+                self.generate_fuel_policies()
+            }
+
+            /// Returns the policy types that will be set on the built transaction.
+            pub fn active_policies(&self) -> Vec<PolicyType> {
+                let policies = self.generate_fuel_policies();
+
+                POLICY_TYPES
+                    .into_iter()
+                    .filter(|policy_type| policies.get(*policy_type).is_some())
+                    .collect()
+            }
+
+            fn base_asset_input_amount(&self) -> Result<u64> {
+                checked_sum(
+                    self.inputs
+                        .iter()
+                        .filter(|input| input.asset_id() == Some(BASE_ASSET_ID))
+                        .filter_map(Input::amount),
+                )
+            }
+
+            fn base_asset_coin_output_amount(&self) -> Result<u64> {
+                checked_sum(self.outputs.iter().filter_map(|output| match output {
+                    Output::Coin {
+                        asset_id, amount, ..
+                    } if *asset_id == BASE_ASSET_ID => Some(*amount),
+                    _ => None,
+                }))
+            }
+
+            // The user witnesses followed by zeroed placeholders as large as the
+            // signatures added during build, enough to compute the fee
+            fn fee_estimation_witnesses(&self) -> Vec<Witness> {
+                let num_signatures = if self.manual_witnesses {
+                    0
+                } else {
+                    self.unresolved_signatures.len()
+                };
+                let placeholders = repeat_with(|| Witness::from(vec![0u8; Signature::LEN]));
+
+                self.witnesses
+                    .iter()
+                    .cloned()
+                    .chain(placeholders.take(num_signatures))
+                    .collect()
+            }
+
+            fn estimate_tx_predicates(&self, tx: &mut $tx_ty) -> Result<()> {
+                if tx.is_using_predicates() {
+                    tx.estimate_predicates(self.consensus_parameters())?;
+                }
+
+                Ok(())
+            }
+
+            fn calculate_fee(&self, tx: FuelTransaction) -> Option<TransactionFee> {
+                self.fee_calculator.fee(
+                    &tx,
+                    self.estimation_gas_costs(),
+                    &self.consensus_parameters().fee_params,
+                )
+            }
+
+            fn estimation_gas_costs(&self) -> &GasCosts {
+                self.gas_costs_override
+                    .as_ref()
+                    .unwrap_or(&self.consensus_parameters().gas_costs)
+            }
+
+            fn effective_gas_price(&self) -> u64 {
+                self.gas_price
+                    .unwrap_or_else(|| self.minimum_acceptable_gas_price())
+            }
+
+            fn is_using_predicates(&self) -> bool {
+                self.inputs()
+                    .iter()
+                    .any(|input| matches!(input, Input::ResourcePredicate { .. }))
             }
 
             fn num_witnesses(&self) -> Result<u8> {
-                let num_witnesses = self.witnesses().len();
+                self.validate_witnesses_count()
+                    .map_err(|err| error!(InvalidData, "{err}"))?;
+
+                Ok(self.witnesses().len() as u8)
+            }
+
+            fn validate(&self) -> Result<()> {
+                self.validate_outputs_count()
+                    .and_then(|_| self.validate_change_outputs())
+                    .and_then(|_| self.validate_contract_outputs())
+                    .and_then(|_| self.validate_resource_kinds())
+                    .and_then(|_| self.validate_coin_maturities())
+                    .and_then(|_| self.validate_gas_price())
+                    .and_then(|_| self.validate_required_signers())
+                    .map_err(|err| error!(InvalidData, "{err}"))
+            }
+
+            fn build_errors(&self) -> Vec<BuildError> {
+                let mut errors = self.missing_signatures();
+                errors.extend(self.insufficient_balances());
+                errors.extend(self.validate_witnesses_count().err());
+                errors.extend(self.validate_outputs_count().err());
+                errors.extend(self.validate_change_outputs().err());
+                errors.extend(self.validate_contract_outputs().err());
+                errors.extend(self.validate_resource_kinds().err());
+                errors.extend(self.validate_coin_maturities().err());
+                errors.extend(self.validate_gas_price().err());
+                errors.extend(self.validate_required_signers().err());
+
+                errors
+            }
+
+            fn missing_signatures(&self) -> Vec<BuildError> {
+                self.inputs
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(input_idx, input)| match input {
+                        Input::ResourceSigned { resource }
+                            if self
+                                .unresolved_signatures
+                                .witness_idx_offset(resource.owner())
+                                .is_none() =>
+                        {
+                            Some(BuildError::MissingSignature {
+                                input_idx,
+                                owner: resource.owner().clone(),
+                            })
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            }
+
+            // Only the fixed amounts of coin outputs are considered, not the fee
+            fn insufficient_balances(&self) -> Vec<BuildError> {
+                let mut balances: BTreeMap<AssetId, (u64, u64)> = BTreeMap::new();
+
+                for input in &self.inputs {
+                    if let (Some(asset_id), Some(amount)) = (input.asset_id(), input.amount()) {
+                        let (available, _) = balances.entry(asset_id).or_default();
+                        *available = available.saturating_add(amount);
+                    }
+                }
+
+                for output in &self.outputs {
+                    if let Output::Coin {
+                        amount, asset_id, ..
+                    } = output
+                    {
+                        let (_, required) = balances.entry(*asset_id).or_default();
+                        *required = required.saturating_add(*amount);
+                    }
+                }
+
+                balances
+                    .into_iter()
+                    .filter(|(_, (available, required))| available < required)
+                    .map(
+                        |(asset_id, (available, required))| BuildError::InsufficientBalance {
+                            asset_id,
+                            available,
+                            required,
+                        },
+                    )
+                    .collect()
+            }
+
+            fn validate_witnesses_count(&self) -> std::result::Result<(), BuildError> {
+                let num_witnesses = self.witnesses().len() + self.unresolved_signatures.len();
+
+                if num_witnesses > 256 {
+                    return Err(BuildError::TooManyWitnesses {
+                        actual: num_witnesses,
+                    });
+                }
+
+                Ok(())
+            }
+
+            fn validate_outputs_count(&self) -> std::result::Result<(), BuildError> {
+                let max_outputs = self.consensus_parameters().tx_params().max_outputs as usize;
+
+                if self.outputs.len() > max_outputs {
+                    return Err(BuildError::TooManyOutputs {
+                        max: max_outputs,
+                        actual: self.outputs.len(),
+                    });
+                }
+
+                Ok(())
+            }
+
+            fn validate_contract_outputs(&self) -> std::result::Result<(), BuildError> {
+                let num_inputs = self.inputs.len();
+
+                // Only contract outputs reference an input
+                for (output_idx, output) in self.outputs.iter().enumerate() {
+                    if let Some(input_idx) = output.input_index() {
+                        if input_idx as usize >= num_inputs {
+                            return Err(BuildError::ContractOutputInputOutOfRange {
+                                output_idx,
+                                input_idx,
+                                num_inputs,
+                            });
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+
+            // A resource added both as signed and as predicate input would be spent twice
+            fn validate_resource_kinds(&self) -> std::result::Result<(), BuildError> {
+                let mut signed_resources: HashMap<CoinTypeId, usize> = HashMap::new();
+                for (idx, input) in self.inputs.iter().enumerate() {
+                    if let Input::ResourceSigned { resource } = input {
+                        signed_resources.entry(resource.id()).or_insert(idx);
+                    }
+                }
+
+                for (predicate_idx, input) in self.inputs.iter().enumerate() {
+                    let Input::ResourcePredicate { resource, .. } = input else {
+                        continue;
+                    };
+
+                    if let Some(signed_idx) = signed_resources.get(&resource.id()) {
+                        return Err(BuildError::SignedAndPredicateResource {
+                            id: resource.id(),
+                            signed_idx: *signed_idx,
+                            predicate_idx,
+                        });
+                    }
+                }
+
+                Ok(())
+            }
+
+            // Nodes reject transactions priced below their minimum, e.g. an explicit `0`
+            fn validate_gas_price(&self) -> std::result::Result<(), BuildError> {
+                let gas_price = self
+                    .raw_policies
+                    .as_ref()
+                    .and_then(|policies| policies.get(PolicyType::GasPrice))
+                    .unwrap_or_else(|| self.effective_gas_price());
+                let min_gas_price = self.network_info.min_gas_price;
+
+                if gas_price < min_gas_price {
+                    return Err(BuildError::GasPriceBelowMinimum {
+                        gas_price,
+                        min_gas_price,
+                    });
+                }
+
+                Ok(())
+            }
+
+            fn validate_required_signers(&self) -> std::result::Result<(), BuildError> {
+                let Some(required_signers) = &self.required_signers else {
+                    return Ok(());
+                };
+
+                if let Some(owner) = required_signers
+                    .iter()
+                    .find(|owner| self.unresolved_signatures.witness_idx_offset(owner).is_none())
+                {
+                    return Err(BuildError::MissingRequiredSigner {
+                        owner: owner.clone(),
+                    });
+                }
+
+                if let Some((owner, _)) = self
+                    .unresolved_signatures
+                    .owners_with_idx_offset()
+                    .find(|(owner, _)| !required_signers.contains(owner))
+                {
+                    return Err(BuildError::UnexpectedSigner {
+                        owner: owner.clone(),
+                    });
+                }
+
+                Ok(())
+            }
+
+            // Coins with a maturity, e.g. coinbase outputs, can only be spent by a tx
+            // whose maturity is at least as high
+            fn validate_coin_maturities(&self) -> std::result::Result<(), BuildError> {
+                let tx_maturity = self.effective_maturity();
+
+                for (input_idx, input) in self.inputs.iter().enumerate() {
+                    let resource = match input {
+                        Input::ResourceSigned { resource }
+                        | Input::ResourcePredicate { resource, .. } => resource,
+                        Input::Contract { .. } => continue,
+                    };
+
+                    if let CoinType::Coin(coin) = resource {
+                        if coin.maturity > tx_maturity {
+                            return Err(BuildError::ImmatureCoin {
+                                input_idx,
+                                coin_maturity: coin.maturity,
+                                tx_maturity,
+                            });
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+
+            // The VM only considers the first change output for a given asset
+            fn validate_change_outputs(&self) -> std::result::Result<(), BuildError> {
+                let mut change_recipients: HashMap<AssetId, (usize, Address)> = HashMap::new();
+
+                for (idx, output) in self.outputs.iter().enumerate() {
+                    let Output::Change { to, asset_id, .. } = output else {
+                        continue;
+                    };
+
+                    match change_recipients.get(asset_id) {
+                        Some((first_idx, first_to)) if first_to != to => {
+                            return Err(BuildError::ConflictingChangeOutputs {
+                                asset_id: *asset_id,
+                                first_idx: *first_idx,
+                                second_idx: idx,
+                                first_to: *first_to,
+                                second_to: *to,
+                            });
+                        }
+                        Some(_) => {}
+                        None => {
+                            change_recipients.insert(*asset_id, (idx, *to));
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+
+            /// Checks that every predicate input spends the base asset or an asset
+            /// found in the outputs, i.e. that no predicate funds an unrelated asset.
+            /// Not part of the regular validation since e.g. a script can forward
+            /// an asset that no output mentions.
+            pub fn validate_predicate_assets(&self) -> std::result::Result<(), BuildError> {
+                let involved_assets: HashSet<AssetId> = self
+                    .outputs
+                    .iter()
+                    .filter_map(|output| output.asset_id().copied())
+                    .chain([BASE_ASSET_ID])
+                    .collect();
+
+                for (input_idx, input) in self.inputs.iter().enumerate() {
+                    let Input::ResourcePredicate { resource, .. } = input else {
+                        continue;
+                    };
+
+                    let asset_id = resource.asset_id();
+                    if !involved_assets.contains(&asset_id) {
+                        return Err(BuildError::UninvolvedPredicateAsset {
+                            input_idx,
+                            asset_id,
+                        });
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+/// What happens when the user defined `script_gas_limit` is higher than the
+/// `max_gas_per_tx` of the network, which the node rejects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverLimitBehavior {
+    /// Lowers the limit to `max_gas_per_tx`.
+    Clamp,
+    /// Fails the build.
+    #[default]
+    Error,
+    /// Keeps the limit as is.
+    Allow,
+}
+
+/// The reusable shape of a script transaction, i.e. everything but its inputs and
+/// signatures. See `ScriptTransactionBuilder::template`.
+#[derive(Debug, Clone)]
+pub struct TransactionTemplate {
+    pub tx_policies: TxPolicies,
+    pub raw_policies: Option<Policies>,
+    pub script: Vec<u8>,
+    pub script_data: Vec<u8>,
+    pub outputs: Vec<Output>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptTransactionBuilder {
+    pub gas_price: Option<u64>,
+    pub gas_limit: Option<u64>,
+    pub witness_limit: Option<u64>,
+    pub max_fee: Option<u64>,
+    pub maturity: u32,
+    pub script: Vec<u8>,
+    pub script_data: Vec<u8>,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+    pub witnesses: Vec<Witness>,
+    pub gas_estimation_tolerance: f32,
+    pub gas_headroom: u64,
+    pub raw_policies: Option<Policies>,
+    pub manual_witnesses: bool,
+    pub gas_costs_override: Option<GasCosts>,
+    pub max_size: Option<usize>,
+    pub tx_pointer: TxPointer,
+    pub required_signers: Option<Vec<Bech32Address>>,
+    pub pure_transfer: bool,
+    pub dry_run_utxo_validation: bool,
+    pub script_padding: Option<usize>,
+    pub deferred_script: bool,
+    pub over_limit_behavior: OverLimitBehavior,
+    pub(crate) network_info: NetworkInfo,
+    unresolved_signatures: UnresolvedSignatures,
+    fallback_fee_input: Option<FallbackFeeInput>,
+    fee_calculator: Arc<dyn FeeCalculator>,
+    post_build_hook: Option<PostBuildHook<Script>>,
+}
+
+// Wraps the hook so that the builders can keep deriving `Debug`
+#[derive(Clone)]
+struct PostBuildHook<T>(Arc<dyn Fn(&mut T) + Send + Sync>);
+
+impl<T> Debug for PostBuildHook<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostBuildHook")
+    }
+}
+
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+struct FallbackFeeInput {
+    #[zeroize(skip)]
+    coin: Coin,
+    secret_key: SecretKey,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTransactionBuilder {
+    pub gas_price: Option<u64>,
+    pub maturity: u32,
+    pub witness_limit: Option<u64>,
+    pub max_fee: Option<u64>,
+    pub bytecode_length: u64,
+    pub bytecode_witness_index: u8,
+    pub storage_slots: Vec<StorageSlot>,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+    pub witnesses: Vec<Witness>,
+    pub salt: Salt,
+    pub raw_policies: Option<Policies>,
+    pub manual_witnesses: bool,
+    pub gas_costs_override: Option<GasCosts>,
+    pub max_size: Option<usize>,
+    pub tx_pointer: TxPointer,
+    pub required_signers: Option<Vec<Bech32Address>>,
+    pub(crate) network_info: NetworkInfo,
+    unresolved_signatures: UnresolvedSignatures,
+    fee_calculator: Arc<dyn FeeCalculator>,
+    post_build_hook: Option<PostBuildHook<Create>>,
+}
+
+impl_tx_trait!(ScriptTransactionBuilder, ScriptTransaction);
+impl_tx_trait!(CreateTransactionBuilder, CreateTransaction);
+
+impl ScriptTransactionBuilder {
+    pub fn new(network_info: NetworkInfo) -> ScriptTransactionBuilder {
+        ScriptTransactionBuilder {
+            gas_price: None,
+            gas_limit: None,
+            witness_limit: None,
+            max_fee: None,
+            maturity: 0,
+            script: vec![],
+            script_data: vec![],
+            inputs: vec![],
+            outputs: vec![],
+            witnesses: vec![],
+            network_info,
+            gas_estimation_tolerance: 0.05,
+            gas_headroom: 0,
+            raw_policies: None,
+            manual_witnesses: false,
+            gas_costs_override: None,
+            max_size: None,
+            tx_pointer: TxPointer::default(),
+            required_signers: None,
+            pure_transfer: false,
+            dry_run_utxo_validation: false,
+            script_padding: None,
+            deferred_script: false,
+            over_limit_behavior: OverLimitBehavior::default(),
+            unresolved_signatures: Default::default(),
+            fallback_fee_input: None,
+            fee_calculator: Arc::new(DefaultFeeCalculator),
+            post_build_hook: None,
+        }
+    }
+
+    /// Recreates the builder of a script transaction from its canonical encoding, e.g.
+    /// an unsigned transaction stored for later signing. Secret keys are not part of
+    /// the encoding, so the signatures have to be added again in the order of the
+    /// witness indexes of the signed inputs for the transaction to keep its id.
+    pub fn rebuild_for_signing(
+        bytes: &[u8],
+        network_info: NetworkInfo,
+    ) -> Result<ScriptTransactionBuilder> {
+        let FuelTransaction::Script(tx) = FuelTransaction::from_bytes(bytes)
+            .map_err(|err| error!(InvalidData, "could not decode the transaction: {err:?}"))?
+        else {
+            return Err(error!(
+                InvalidData,
+                "only script transactions can be rebuilt for signing"
+            ));
+        };
+
+        // The signatures are placed after the user provided witnesses
+        let num_user_witnesses = tx
+            .inputs()
+            .iter()
+            .filter_map(FuelInput::witness_index)
+            .min()
+            .map_or(tx.witnesses().len(), usize::from);
+
+        Ok(Self::new(network_info)
+            .with_script(tx.script().clone())
+            .with_script_data(tx.script_data().clone())
+            .with_gas_limit(*tx.script_gas_limit())
+            .with_raw_policies(*tx.policies())
+            .with_inputs(
+                tx.inputs()
+                    .iter()
+                    .cloned()
+                    .map(unresolve_fuel_input)
+                    .collect(),
+            )
+            .with_outputs(tx.outputs().clone())
+            .with_witnesses(
+                tx.witnesses()
+                    .iter()
+                    .take(num_user_witnesses)
+                    .cloned()
+                    .collect(),
+            ))
+    }
+
+    async fn build(self, provider: impl DryRunner) -> Result<ScriptTransaction> {
+        let tb = self.resolve_fallback_fee_input(&provider).await?;
+        tb.build_without_fallback_fee_input(provider).await
+    }
+
+    async fn build_without_fallback_fee_input(
+        self,
+        provider: impl DryRunner,
+    ) -> Result<ScriptTransaction> {
+        let requires_dry_run = self.requires_dry_run();
+        let network_info = self.network_info.clone();
+        let tolerance = self.gas_estimation_tolerance;
+        let gas_headroom = self.gas_headroom;
+        let utxo_validation = self.dry_run_utxo_validation;
+        let max_size = self.max_size;
+
+        let mut unsigned_tx = self.resolve_unsigned_fuel_tx()?;
+
+        if requires_dry_run && utxo_validation {
+            validate_gas_estimation_tolerance(tolerance)?;
+            Self::set_script_gas_limit_to_validated_gas_used(
+                &mut unsigned_tx,
+                &provider,
+                &network_info,
+                tolerance,
+                gas_headroom,
+            )
+            .await?;
+        } else if requires_dry_run {
+            validate_gas_estimation_tolerance(tolerance)?;
+            Self::set_script_gas_limit_to_gas_used(
+                &mut unsigned_tx.tx,
+                &provider,
+                &network_info,
+                tolerance,
+                gas_headroom,
+            )
+            .await?;
+        }
+
+        let tx = unsigned_tx
+            .sign_with_external_signers(&network_info.chain_id())
+            .await?;
+        validate_tx_size(tx.tx.size(), max_size)?;
+
+        Ok(tx)
+    }
+
+    /// Builds the transaction and simulates it with a single dry run. If the
+    /// `script_gas_limit` has to be estimated, it is derived from the simulated
+    /// gas instead of dry running once more.
+    pub async fn simulate_and_build(
+        self,
+        provider: impl DetailedDryRunner,
+    ) -> Result<(ScriptTransaction, SimulationResult)> {
+        let tb = self.resolve_fallback_fee_input(&provider).await?;
+        let utxo_validation = tb.dry_run_utxo_validation;
+
+        if !tb.requires_dry_run() {
+            let tx = tb.build_without_fallback_fee_input(&provider).await?;
+            let receipts = if utxo_validation {
+                provider
+                    .dry_run_and_get_receipts_with_utxo_validation(tx.clone().into())
+                    .await?
+            } else {
+                provider.dry_run_and_get_receipts(tx.clone().into()).await?
+            };
+
+            return Ok((tx, SimulationResult::from_receipts(receipts)?));
+        }
+
+        let network_info = tb.network_info.clone();
+        let tolerance = tb.gas_estimation_tolerance;
+        let gas_headroom = tb.gas_headroom;
+        let max_size = tb.max_size;
+        validate_gas_estimation_tolerance(tolerance)?;
+
+        let mut unsigned_tx = tb.resolve_unsigned_fuel_tx()?;
+
+        let receipts = if utxo_validation {
+            // The node checks the signatures, so the tx is signed and has no temporary coin
+            Self::set_max_script_gas_limit(&mut unsigned_tx.tx, &network_info);
+            let signed_tx = unsigned_tx
+                .clone()
+                .sign_with_external_signers(&network_info.chain_id())
+                .await?;
+
+            provider
+                .dry_run_and_get_receipts_with_utxo_validation(signed_tx.into())
+                .await?
+        } else {
+            Self::prepare_gas_estimation(&mut unsigned_tx.tx, &network_info);
+            let receipts = provider
+                .dry_run_and_get_receipts(unsigned_tx.tx.clone().into())
+                .await?;
+            // Remove the temporary coin
+            unsigned_tx.tx.inputs_mut().pop();
+
+            receipts
+        };
+
+        let simulation = SimulationResult::from_receipts(receipts)?;
+        // Same tolerance as the one applied by the `DryRunner`
+        let gas_used = (simulation.gas_used as f64 * (1.0 + tolerance as f64)) as u64;
+        unsigned_tx
+            .tx
+            .set_script_gas_limit(Self::script_gas_limit_with_headroom(
+                gas_used,
+                gas_headroom,
+                &network_info,
+            ));
+
+        let tx = unsigned_tx
+            .sign_with_external_signers(&network_info.chain_id())
+            .await?;
+        validate_tx_size(tx.tx.size(), max_size)?;
+
+        Ok((tx, simulation))
+    }
+
+    /// Builds and dry runs the transaction, returning its receipts, logs and
+    /// return data. Nothing is broadcast, making it suitable for previews.
+    pub async fn simulate(&self, provider: impl DetailedDryRunner) -> Result<SimulationResult> {
+        let tx = self.clone().build(&provider).await?;
+        let receipts = provider.dry_run_and_get_receipts(tx.into()).await?;
+
+        SimulationResult::from_receipts(receipts)
+    }
+
+    /// Builds and dry runs the transaction, returning the contract storage it would
+    /// change. Requires a `provider` reporting storage changes.
+    pub async fn preview_storage_changes(
+        &self,
+        provider: impl DetailedDryRunner,
+    ) -> Result<Vec<StorageChange>> {
+        let tx = self.clone().build(&provider).await?;
+
+        provider.dry_run_and_get_storage_changes(tx.into()).await
+    }
+
+    /// Build the transaction without a `DryRunner`. Fails if the `script_gas_limit`
+    /// would have to be estimated, if a predicate input does not provide its
+    /// `predicate_gas_used` or if external signers were added.
+    pub fn build_offline(mut self) -> Result<ScriptTransaction> {
+        if self.requires_dry_run() {
+            return Err(error!(
+                InvalidData,
+                "`script_gas_limit` has to be set to build offline"
+            ));
+        }
+
+        if self.fallback_fee_input.is_some() {
+            let max_fee = self
+                .fee_at_gas_price(self.effective_gas_price(), self.resolved_script_gas_limit())?
+                .max_fee();
+            self.include_fallback_fee_input_if_needed(max_fee)?;
+        }
+
+        if !self.unresolved_signatures.external_signers.is_empty() {
+            return Err(error!(
+                InvalidData,
+                "external signers can not be used to build offline"
+            ));
+        }
+
+        validate_predicates_gas_used(&self.inputs)?;
+
+        let chain_id = self.network_info.chain_id();
+        let max_size = self.max_size;
+        let tx = self.resolve_unsigned_fuel_tx()?.sign(&chain_id, vec![]);
+        validate_tx_size(tx.tx.size(), max_size)?;
+
+        Ok(tx)
+    }
+
+    /// Builds offline using `gas_used` as the `script_gas_limit`. Signatures are
+    /// deterministic so, given the same secret keys, the result is byte-identical
+    /// across invocations, e.g. for replay tests without a live dry runner.
+    #[cfg(any(test, feature = "test-helpers"))]
+    pub fn build_deterministic(mut self, gas_used: u64) -> Result<ScriptTransaction> {
+        self.gas_limit = Some(gas_used);
+
+        self.build_offline()
+    }
+
+    async fn resolve_fallback_fee_input(mut self, provider: &impl DryRunner) -> Result<Self> {
+        if self.fallback_fee_input.is_none() {
+            return Ok(self);
+        }
+
+        let max_fee = self
+            .fee_checked_from_tx(provider)
+            .await?
+            .ok_or(error!(InvalidData, "error calculating `TransactionFee`"))?
+            .max_fee();
+        self.include_fallback_fee_input_if_needed(max_fee)?;
+
+        Ok(self)
+    }
+
+    // The fallback input is only spent if the base asset inputs do not cover the
+    // coin outputs and the fee estimated without it
+    fn include_fallback_fee_input_if_needed(&mut self, max_fee: u64) -> Result<()> {
+        let Some(fallback) = self.fallback_fee_input.take() else {
+            return Ok(());
+        };
+
+        let required = checked_sum([self.base_asset_coin_output_amount()?, max_fee])?;
+        if self.base_asset_input_amount()? >= required {
+            return Ok(());
+        }
+
+        let owner = fallback.coin.owner.clone();
+        if self.witness_index_for(&owner).is_none() {
+            self.add_unresolved_signature(owner, fallback.secret_key);
+        }
+        self.inputs.push(Input::resource_signed(CoinType::Coin(
+            fallback.coin.clone(),
+        )));
+
+        Ok(())
+    }
+
+    fn validate_script_padding(&self) -> Result<()> {
+        match self.script_padding {
+            Some(target_len) if self.script.len() != target_len => Err(error!(
+                InvalidData,
+                "script is `{}` bytes long but has to be padded to `{target_len}` bytes",
+                self.script.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_script_gas_limit(&self) -> Result<()> {
+        let max_gas_per_tx = self.network_info.max_gas_per_tx();
+
+        match self.gas_limit {
+            Some(gas_limit)
+                if !self.script.is_empty()
+                    && gas_limit > max_gas_per_tx
+                    && self.over_limit_behavior == OverLimitBehavior::Error =>
+            {
+                Err(error!(
+                    InvalidData,
+                    "`script_gas_limit` `{gas_limit}` is higher than the max gas per tx of `{max_gas_per_tx}`"
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_deferred_script(&self) -> Result<()> {
+        if self.deferred_script {
+            return Err(error!(
+                InvalidData,
+                "the script was deferred but never filled with `fill_script`"
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_pure_transfer(&self) -> Result<()> {
+        if self.pure_transfer && !self.script.is_empty() {
+            return Err(error!(
+                InvalidData,
+                "a script was added to a transaction marked as a pure transfer"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Share of the per-tx gas budget taken by the tx with the given `script_gas_limit`,
+    /// i.e. `(min_gas + script_gas_limit) / max_gas_per_tx`.
+    pub fn gas_budget_utilization(&self, script_gas_limit: u64) -> Result<f32> {
+        let tx = self.resolve_fuel_tx_for_fee(script_gas_limit)?;
+        let min_gas = tx.min_gas(
+            self.estimation_gas_costs(),
+            self.consensus_parameters().fee_params(),
+        );
+
+        Ok(min_gas.saturating_add(script_gas_limit) as f32
+            / self.network_info.max_gas_per_tx() as f32)
+    }
+
+    /// Splits the gas part of the fee into `(script_gas_fee, predicate_gas_fee)`, i.e.
+    /// the fee of the `script_gas_limit`, dry run if needed, and of the gas used by
+    /// the predicates, estimated the same way as in `fee_checked_from_tx`. The fee of
+    /// the tx size and signatures is not part of either component.
+    pub async fn fee_components(&self, provider: impl DryRunner) -> Result<(u64, u64)> {
+        let mut tx = self.resolve_fee_estimation_tx(provider).await?;
+        self.estimate_tx_predicates(&mut tx)?;
+        let predicate_gas = tx
+            .tx
+            .inputs()
+            .iter()
+            .filter_map(FuelInput::predicate_gas_used)
+            .try_fold(0u64, u64::checked_add)
+            .ok_or(error!(InvalidData, "predicate gas overflows `u64`"))?;
+
+        let gas_price = self.effective_gas_price();
+        let fee_params = self.consensus_parameters().fee_params();
+        let to_fee = |gas| {
+            gas_to_fee(gas, gas_price, fee_params)
+                .ok_or(error!(InvalidData, "fee of `{gas}` gas overflows `u64`"))
+        };
+
+        Ok((to_fee(*tx.tx.script_gas_limit())?, to_fee(predicate_gas)?))
+    }
+
+    /// True only when the script is not empty and the `script_gas_limit` was not set,
+    /// the one case in which it is estimated with a dry run.
+    pub fn requires_dry_run(&self) -> bool {
+        !self.script.is_empty() && self.gas_limit.is_none()
+    }
+
+    // When dry running a tx with `utxo_validation` off, the node will not validate signatures.
+    // However, the node will check if the right number of witnesses is present.
+    // This function will create empty witnesses such that the total length matches the expected one.
+    fn create_dry_run_witnesses(&self, num_witnesses: u8) -> Vec<Witness> {
+        let unresolved_witnesses_len = self.unresolved_signatures.len();
+        repeat_with(Default::default)
+            // Add one in case there is no witnesses at all
+            .take(max(num_witnesses as usize + unresolved_witnesses_len, 1))
+            .collect()
+    }
+
+    // When the `script_gas_limit` was not set by the user, `dry_run` the tx
+    // and set the `script_gas_limit` to the actual `gas_used`
+    async fn set_script_gas_limit_to_gas_used(
+        tx: &mut Script,
+        provider: &impl DryRunner,
+        network_info: &NetworkInfo,
+        tolerance: f32,
+        gas_headroom: u64,
+    ) -> Result<()> {
+        Self::prepare_gas_estimation(tx, network_info);
+
+        let gas_used = provider
+            .dry_run_and_get_used_gas(tx.clone().into(), tolerance)
+            .await?;
+
+        // Remove the temporary coin
+        tx.inputs_mut().pop();
+
+        tx.set_script_gas_limit(Self::script_gas_limit_with_headroom(
+            gas_used,
+            gas_headroom,
+            network_info,
+        ));
+
+        Ok(())
+    }
+
+    // Same as `set_script_gas_limit_to_gas_used` but dry runs the signed tx so that
+    // the node can validate its UTXOs and signatures. No temporary coin is added
+    // since it would not pass the validation.
+    async fn set_script_gas_limit_to_validated_gas_used(
+        unsigned_tx: &mut UnsignedScript,
+        provider: &impl DryRunner,
+        network_info: &NetworkInfo,
+        tolerance: f32,
+        gas_headroom: u64,
+    ) -> Result<()> {
+        Self::set_max_script_gas_limit(&mut unsigned_tx.tx, network_info);
+
+        let signed_tx = unsigned_tx
+            .clone()
+            .sign_with_external_signers(&network_info.chain_id())
+            .await?;
+        let gas_used = provider
+            .dry_run_and_get_used_gas_with_utxo_validation(signed_tx.into(), tolerance)
+            .await?;
+
+        unsigned_tx
+            .tx
+            .set_script_gas_limit(Self::script_gas_limit_with_headroom(
+                gas_used,
+                gas_headroom,
+                network_info,
+            ));
+
+        Ok(())
+    }
+
+    // The headroom is added on top of the tolerance but can not exceed the gas
+    // available to a single tx
+    fn script_gas_limit_with_headroom(
+        gas_used: u64,
+        gas_headroom: u64,
+        network_info: &NetworkInfo,
+    ) -> u64 {
+        gas_used
+            .saturating_add(gas_headroom)
+            .min(network_info.max_gas_per_tx())
+    }
+
+    fn set_max_script_gas_limit(tx: &mut Script, network_info: &NetworkInfo) {
+        let consensus_params = &network_info.consensus_parameters;
+        let fee_params = consensus_params.fee_params();
+        let max_gas = tx.max_gas(consensus_params.gas_costs(), fee_params)
+            + gas_rounding_margin(tx.price(), fee_params);
+
+        // TODO: @xgreenx why do I need to / 2
+        tx.set_script_gas_limit((network_info.max_gas_per_tx() / 2).saturating_sub(max_gas));
+    }
+
+    // Sets the highest possible `script_gas_limit` and adds a temporary coin
+    // that has to be popped once the tx was dry run
+    fn prepare_gas_estimation(tx: &mut Script, network_info: &NetworkInfo) {
+        Self::set_max_script_gas_limit(tx, network_info);
+
+        // The `dry_run` validation will check if there is an input present that can cover
+        // the tx fees. If we are estimating without inputs we have to add a temporary one.
+        // Fees are paid in the base asset so the coin has to hold it
+        tx.inputs_mut().push(FuelInput::coin_signed(
+            Default::default(),
+            Default::default(),
+            1_000_000_000,
+            BASE_ASSET_ID,
+            TxPointer::default(),
+            0,
+            0u32.into(),
+        ));
+    }
+
+    fn resolve_unsigned_fuel_tx(self) -> Result<UnsignedScript> {
+        let tx = self.resolve_fuel_tx()?;
+        let is_using_predicates = self.is_using_predicates();
+
+        // Without signatures to resolve `sign` only sets the user provided witnesses
+        let unresolved_signatures = if self.manual_witnesses {
+            Default::default()
+        } else {
+            self.unresolved_signatures
+        };
+
+        Ok(UnsignedScript {
+            tx,
+            is_using_predicates,
+            witnesses: self.witnesses,
+            unresolved_signatures,
+            post_build_hook: self.post_build_hook,
+        })
+    }
+
+    // Borrows the builder, cloning only what ends up in the tx
+    fn resolve_fuel_tx(&self) -> Result<Script> {
+        self.validate()?;
+        self.validate_pure_transfer()?;
+        self.validate_script_padding()?;
+        self.validate_deferred_script()?;
+        self.validate_script_gas_limit()?;
+
+        let num_witnesses = self.num_witnesses()?;
+
+        Ok(FuelTransaction::script(
+            self.resolved_script_gas_limit(),
+            self.script.clone(),
+            self.script_data.clone(),
+            self.generate_fuel_policies(),
+            resolve_fuel_inputs(
+                self.inputs.iter().cloned(),
+                self.inputs_offset(),
+                num_witnesses,
+                &self.unresolved_signatures,
+                self.tx_pointer,
+            )?,
+            self.outputs.clone(),
+            self.create_dry_run_witnesses(num_witnesses),
+        ))
+    }
+
+    // Zero without a script, otherwise the user defined value even if it makes the
+    // tx revert, clamped according to the `over_limit_behavior`. Overwritten by the
+    // estimation when a dry run is required
+    fn resolved_script_gas_limit(&self) -> u64 {
+        if self.script.is_empty() {
+            return 0;
+        }
+
+        let gas_limit = self.gas_limit.unwrap_or_default();
+        match self.over_limit_behavior {
+            OverLimitBehavior::Clamp => gas_limit.min(self.network_info.max_gas_per_tx()),
+            OverLimitBehavior::Error | OverLimitBehavior::Allow => gas_limit,
+        }
+    }
+
+    // Like `build` but without signing or cloning the builder
+    async fn resolve_fee_estimation_tx(
+        &self,
+        provider: impl DryRunner,
+    ) -> Result<ScriptTransaction> {
+        let mut tx = self.resolve_fuel_tx()?;
+
+        if self.requires_dry_run() {
+            validate_gas_estimation_tolerance(self.gas_estimation_tolerance)?;
+            Self::set_script_gas_limit_to_gas_used(
+                &mut tx,
+                &provider,
+                &self.network_info,
+                self.gas_estimation_tolerance,
+                self.gas_headroom,
+            )
+            .await?;
+        }
+        *tx.witnesses_mut() = self.fee_estimation_witnesses();
+
+        Ok(ScriptTransaction {
+            tx,
+            is_using_predicates: self.is_using_predicates(),
+        })
+    }
+
+    fn resolve_fuel_tx_for_fee(&self, gas_used: u64) -> Result<Script> {
+        let mut tx = self.resolve_fuel_tx()?;
+
+        if !self.script.is_empty() {
+            tx.set_script_gas_limit(gas_used);
+        }
+        *tx.witnesses_mut() = self.fee_estimation_witnesses();
+
+        Ok(tx)
+    }
+
+    fn generate_fuel_policies(&self) -> Policies {
+        let mut policies = self.generate_shared_fuel_policies();
+        policies.set(
+            PolicyType::WitnessLimit,
+            self.witness_limit.or(Some(DEFAULT_SCRIPT_WITNESS_LIMIT)),
+        );
+        self.apply_raw_policies(&mut policies);
+
+        policies
+    }
+
+    fn base_offset(&self) -> usize {
+        offsets::base_offset_script(self.consensus_parameters())
+            + padded_len_usize(self.script_data.len())
+            + padded_len_usize(self.script.len())
+    }
+
+    // Offset at which the first input starts
+    fn inputs_offset(&self) -> usize {
+        self.base_offset() + self.generate_fuel_policies().size_dynamic()
+    }
+
+    // The id does not depend on the witnesses, only the `script_gas_limit` has to be final
+    fn preview_tx_id(&self, chain_id: &ChainId) -> Result<Bytes32> {
+        if self.requires_dry_run() {
+            return Err(error!(
+                InvalidData,
+                "`script_gas_limit` has to be set to preview the transaction id"
+            ));
+        }
+
+        Ok(self.resolve_fuel_tx()?.id(chain_id))
+    }
+
+    pub fn with_script(mut self, script: Vec<u8>) -> Self {
+        self.script = script;
+        self
+    }
+
+    /// Appends `noop` instructions to the script until it is `target_len` bytes long,
+    /// so that the offsets of the script data and the inputs do not depend on the
+    /// script. Building fails if the script ends up with a different length, e.g.
+    /// because it was already longer than `target_len`.
+    pub fn with_script_padding(mut self, target_len: usize) -> Self {
+        let num_noops = target_len.saturating_sub(self.script.len()) / Instruction::SIZE;
+        self.script
+            .extend(repeat_with(op::noop).take(num_noops).collect::<Vec<u8>>());
+        self.script_padding = Some(target_len);
+        self
+    }
+
+    /// Like `with_script_padding` but fails right away if `target_len` is not a
+    /// multiple of the instruction size or if the script is already longer.
+    pub fn try_with_script_padding(self, target_len: usize) -> Result<Self> {
+        if target_len % Instruction::SIZE != 0 {
+            return Err(error!(
+                InvalidData,
+                "script padding `{target_len}` is not a multiple of the instruction size `{}`",
+                Instruction::SIZE
+            ));
+        }
+        if self.script.len() > target_len {
+            return Err(error!(
+                InvalidData,
+                "script is `{}` bytes long and can not be padded to `{target_len}` bytes",
+                self.script.len()
+            ));
+        }
+
+        Ok(self.with_script_padding(target_len))
+    }
+
+    /// Decodes the script into one human-readable instruction per line, e.g. to review
+    /// a hand-written script. Fails if the script contains an invalid instruction.
+    pub fn disassemble_script(&self) -> Result<Vec<String>> {
+        if self.script.len() % Instruction::SIZE != 0 {
+            return Err(error!(
+                InvalidData,
+                "script length `{}` is not a multiple of the instruction size",
+                self.script.len()
+            ));
+        }
+
+        fuel_asm::from_bytes(self.script.iter().copied())
+            .enumerate()
+            .map(|(idx, instruction)| {
+                instruction
+                    .map(|instruction| format!("{instruction:?}"))
+                    .map_err(|err| {
+                        error!(
+                            InvalidData,
+                            "invalid instruction at index `{idx}` of the script: {err:?}"
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    pub fn with_script_data(mut self, script_data: Vec<u8>) -> Self {
+        self.script_data = script_data;
+        self
+    }
+
+    /// Marks the script as determined later with `fill_script`, e.g. by another stage
+    /// of a pipeline. Building fails until it is filled.
+    pub fn with_deferred_script(mut self) -> Self {
+        self.deferred_script = true;
+        self
+    }
+
+    /// Sets the script and script data of a builder created `with_deferred_script`.
+    /// Fails if the script was not deferred or was already filled.
+    pub fn fill_script(&mut self, script: Vec<u8>, script_data: Vec<u8>) -> Result<()> {
+        if !self.deferred_script {
+            return Err(error!(InvalidData, "only a deferred script can be filled"));
+        }
+
+        self.script = script;
+        self.script_data = script_data;
+        self.deferred_script = false;
+
+        Ok(())
+    }
+
+    pub fn with_gas_estimation_tolerance(mut self, tolerance: f32) -> Self {
+        self.gas_estimation_tolerance = tolerance;
+        self
+    }
+
+    /// Dry runs the tx `samples` times and returns the tolerance covering the spread
+    /// of the observed `gas_used` plus a small margin, capped at `1.0`. The result can
+    /// be passed to `with_gas_estimation_tolerance`.
+    pub async fn calibrate_gas_tolerance(
+        &self,
+        provider: impl DryRunner,
+        samples: usize,
+    ) -> Result<f32> {
+        const MARGIN: f32 = 0.01;
+
+        if samples == 0 {
+            return Err(error!(
+                InvalidData,
+                "at least one sample is needed to calibrate the gas estimation tolerance"
+            ));
+        }
+
+        let mut tx = self.resolve_fuel_tx()?;
+        Self::prepare_gas_estimation(&mut tx, &self.network_info);
+
+        let mut gas_used = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            gas_used.push(
+                provider
+                    .dry_run_and_get_used_gas(tx.clone().into(), 0.0)
+                    .await?,
+            );
+        }
+
+        let (min, max) = gas_used
+            .into_iter()
+            .minmax()
+            .into_option()
+            .expect("there is at least one sample");
+        let spread = (max - min) as f32 / min.max(1) as f32;
+
+        Ok((spread + MARGIN).min(1.0))
+    }
+
+    /// Adds `extra_gas` to the estimated gas when the `script_gas_limit` is set with a
+    /// dry run, e.g. to absorb state changes between the estimation and the execution.
+    /// Applied after the `gas_estimation_tolerance` and capped at the max gas per tx.
+    pub fn with_gas_headroom(mut self, extra_gas: u64) -> Self {
+        self.gas_headroom = extra_gas;
+        self
+    }
+
+    /// Like `with_gas_estimation_tolerance` but fails if the tolerance is not a
+    /// finite value in the range `0.0..=1.0`.
+    pub fn try_with_gas_estimation_tolerance(self, tolerance: f32) -> Result<Self> {
+        validate_gas_estimation_tolerance(tolerance)?;
+
+        Ok(self.with_gas_estimation_tolerance(tolerance))
+    }
+
+    /// Sets a coin that `build` only spends, signing it with `secret_key`, if the
+    /// other base asset inputs do not cover the coin outputs and the estimated fee.
+    /// This avoids spending an extra UTXO when it is not needed.
+    pub fn with_fallback_fee_input(mut self, coin: Coin, secret_key: SecretKey) -> Self {
+        self.fallback_fee_input = Some(FallbackFeeInput { coin, secret_key });
+        self
+    }
+
+    /// Lets the node validate the UTXOs and signatures when dry running to estimate
+    /// the `script_gas_limit`, e.g. for scripts or predicates reading the signatures.
+    /// The estimated transaction is signed, so external signers are asked to sign
+    /// twice. Off by default.
+    pub fn with_dry_run_utxo_validation(mut self, utxo_validation: bool) -> Self {
+        self.dry_run_utxo_validation = utxo_validation;
+        self
+    }
+
+    /// Marks the transaction as a pure transfer that never dry runs when built.
+    /// Building fails if the script is not empty.
+    pub fn as_pure_transfer(mut self) -> Self {
+        self.pure_transfer = true;
+        self
+    }
+
+    pub fn prepare_transfer(
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        ScriptTransactionBuilder::new(network_info)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_tx_policies(tx_policies)
+    }
+
+    /// Captures the policies, script and outputs of the builder, e.g. to issue many
+    /// similar transactions that only differ in how they are funded.
+    pub fn template(&self) -> TransactionTemplate {
+        TransactionTemplate {
+            tx_policies: TxPolicies::new(
+                self.gas_price,
+                self.witness_limit,
+                self.maturity,
+                self.max_fee,
+                self.gas_limit,
+            ),
+            raw_policies: self.raw_policies,
+            script: self.script.clone(),
+            script_data: self.script_data.clone(),
+            outputs: self.outputs.clone(),
+        }
+    }
+
+    /// Creates a builder from `template` spending `inputs`, signed by `secret_keys`.
+    pub fn instantiate(
+        template: &TransactionTemplate,
+        inputs: Vec<Input>,
+        secret_keys: Vec<SecretKey>,
+        network_info: NetworkInfo,
+    ) -> Self {
+        let mut tb = ScriptTransactionBuilder::new(network_info)
+            .with_tx_policies(template.tx_policies)
+            .with_script(template.script.clone())
+            .with_script_data(template.script_data.clone())
+            .with_inputs(inputs)
+            .with_outputs(template.outputs.clone());
+        tb.raw_policies = template.raw_policies;
+
+        for secret_key in secret_keys {
+            let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+            tb.add_unresolved_signature(owner, secret_key);
+        }
+
+        tb
+    }
+
+    /// Craft a transaction that sweeps `resources`, possibly held by several owners,
+    /// into a single change output per asset sent to `to`. Every owner has to be
+    /// covered by one of the `secret_keys` and signs once for all of its inputs.
+    pub fn prepare_consolidation(
+        resources: Vec<CoinType>,
+        to: Address,
+        secret_keys: Vec<SecretKey>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Result<Self> {
+        let mut signers: HashMap<Bech32Address, SecretKey> = secret_keys
+            .into_iter()
+            .map(|secret_key| {
+                let owner =
+                    Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+                (owner, secret_key)
+            })
+            .collect();
+
+        // Every change output receives the total amount of its asset
+        for amounts in resources
+            .iter()
+            .map(|resource| (resource.asset_id(), resource.amount()))
+            .into_group_map()
+            .into_values()
+        {
+            checked_sum(amounts)?;
+        }
+
+        let outputs = resources
+            .iter()
+            .map(CoinType::asset_id)
+            .unique()
+            .map(|asset_id| Output::change(to, 0, asset_id))
+            .collect();
+        let mut tb =
+            ScriptTransactionBuilder::prepare_transfer(vec![], outputs, tx_policies, network_info);
+
+        for resource in resources {
+            let owner = resource.owner();
+            if tb.witness_index_for(owner).is_none() {
+                let secret_key = signers.remove(owner).ok_or(error!(
+                    InvalidData,
+                    "no secret key provided for owner: `{owner}`"
+                ))?;
+                tb.add_unresolved_signature(owner.clone(), secret_key);
+            }
+
+            tb.inputs.push(Input::resource_signed(resource));
+        }
+
+        Ok(tb)
+    }
+
+    /// Splits the transaction into several ones that respect the consensus limits of
+    /// inputs, outputs and witnesses, e.g. to sweep more coins than fit in a single
+    /// transaction. Inputs are distributed in order and every sub-transaction only
+    /// signs for the owners of its inputs. Contract outputs follow their contract
+    /// input, with a rebased `input_index`, change outputs are added to every
+    /// sub-transaction spending their asset and coin outputs to the first one whose
+    /// inputs can fund them, not counting the fee. All other outputs and the
+    /// witnesses added with `with_witnesses` are kept in the first sub-transaction.
+    pub fn split_by_limits(self) -> Result<Vec<Self>> {
+        if !self.unresolved_signatures.external_signers.is_empty() {
+            return Err(error!(
+                InvalidData,
+                "transactions with external signers can not be split"
+            ));
+        }
+        if self.inputs.is_empty() {
+            return Ok(vec![self]);
+        }
+
+        let tx_params = self.consensus_parameters().tx_params();
+        let max_inputs = tx_params.max_inputs as usize;
+        let max_outputs = tx_params.max_outputs as usize;
+        let max_witnesses = tx_params.max_witnesses as usize;
+
+        let mut empty_batch = self.clone();
+        empty_batch.inputs.clear();
+        empty_batch.outputs.clear();
+        empty_batch.witnesses.clear();
+        empty_batch.clear_signatures();
+        // Spending the fallback coin in several transactions would be a double spend
+        empty_batch.fallback_fee_input = None;
+
+        let mut first_batch = empty_batch.clone();
+        first_batch.witnesses = self.witnesses.clone();
+        first_batch.fallback_fee_input = self.fallback_fee_input.clone();
+        let first_batch_outputs = self
+            .outputs
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, output)| {
+                !matches!(
+                    output,
+                    Output::Contract(_) | Output::Change { .. } | Output::Coin { .. }
+                )
+            })
+            .collect();
+
+        // The outputs are kept with their index in `self.outputs` to preserve their order
+        let mut batches: Vec<(Self, Vec<(usize, Output)>)> =
+            vec![(first_batch, first_batch_outputs)];
+        for (input_idx, input) in self.inputs.iter().enumerate() {
+            let signer = match input {
+                Input::ResourceSigned { resource } => Some(resource.owner()),
+                _ => None,
+            };
+            let contract_outputs: Vec<_> = self
+                .outputs
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|(_, output)| output.input_index().map(usize::from) == Some(input_idx))
+                .collect();
+            let change_output = input.asset_id().and_then(|input_asset_id| {
+                self.outputs.iter().copied().enumerate().find(|(_, output)| {
+                    matches!(output, Output::Change { asset_id, .. } if *asset_id == input_asset_id)
+                })
+            });
+
+            let (batch, batch_outputs) = batches.last().expect("there is a first batch");
+            let adds_witness = signer.is_some_and(|owner| batch.witness_index_for(owner).is_none());
+            let num_witnesses = batch.witnesses.len()
+                + batch.unresolved_signatures.len()
+                + usize::from(adds_witness);
+            let adds_change = change_output.is_some_and(|(idx, _)| {
+                batch_outputs.iter().all(|(other_idx, _)| *other_idx != idx)
+            });
+            let num_outputs =
+                batch_outputs.len() + contract_outputs.len() + usize::from(adds_change);
+            let fits_in_last_batch = batch.inputs.is_empty()
+                || (batch.inputs.len() < max_inputs
+                    && num_witnesses <= max_witnesses
+                    && num_outputs <= max_outputs);
+            if !fits_in_last_batch {
+                batches.push((empty_batch.clone(), vec![]));
+            }
+            let (batch, batch_outputs) = batches.last_mut().expect("there is a last batch");
+
+            if let Some(owner) = signer.filter(|owner| batch.witness_index_for(owner).is_none()) {
+                let witness_idx_offset = self
+                    .unresolved_signatures
+                    .addr_idx_offset_map
+                    .get(owner)
+                    .ok_or(error!(
+                        InvalidData,
+                        "signature missing for input with owner: `{owner}`"
+                    ))?;
+                let secret_key =
+                    self.unresolved_signatures.secret_keys[*witness_idx_offset as usize];
+                batch.add_unresolved_signature(owner.clone(), secret_key);
+            }
+
+            let batch_input_idx = batch.inputs.len() as u8;
+            batch.inputs.push(input.clone());
+            batch_outputs.extend(contract_outputs.into_iter().map(|(idx, mut output)| {
+                if let Output::Contract(contract) = &mut output {
+                    contract.input_index = batch_input_idx;
+                }
+                (idx, output)
+            }));
+            if let Some(change_output) = change_output
+                .filter(|(idx, _)| batch_outputs.iter().all(|(other_idx, _)| other_idx != idx))
+            {
+                batch_outputs.push(change_output);
+            }
+        }
+
+        for (output_idx, output) in self.outputs.iter().copied().enumerate() {
+            let Output::Coin {
+                amount, asset_id, ..
+            } = output
+            else {
+                continue;
+            };
+
+            let unassigned_amount = |batch: &Self, batch_outputs: &[(usize, Output)]| {
+                let input_amount: u128 = batch
+                    .inputs
+                    .iter()
+                    .filter(|input| input.asset_id() == Some(asset_id))
+                    .map(|input| input.amount().unwrap_or_default() as u128)
+                    .sum();
+                let output_amount: u128 = batch_outputs
+                    .iter()
+                    .filter_map(|(_, output)| match output {
+                        Output::Coin {
+                            amount,
+                            asset_id: output_asset_id,
+                            ..
+                        } if *output_asset_id == asset_id => Some(*amount as u128),
+                        _ => None,
+                    })
+                    .sum();
+
+                input_amount.saturating_sub(output_amount)
+            };
+            let (_, batch_outputs) = batches
+                .iter_mut()
+                .find(|(batch, batch_outputs)| {
+                    batch_outputs.len() < max_outputs
+                        && unassigned_amount(batch, batch_outputs) >= amount as u128
+                })
+                .ok_or(error!(
+                    InvalidData,
+                    "coin output of `{amount}` of asset `{asset_id}` can not be funded by the inputs of a single split transaction"
+                ))?;
+            batch_outputs.push((output_idx, output));
+        }
+
+        batches
+            .into_iter()
+            .map(|(mut batch, mut batch_outputs)| {
+                if batch_outputs.len() > max_outputs {
+                    return Err(error!(
+                        InvalidData,
+                        "outputs of a split transaction exceed the limit of `{max_outputs}`"
+                    ));
+                }
+
+                batch_outputs.sort_by_key(|(idx, _)| *idx);
+                batch.outputs = batch_outputs
+                    .into_iter()
+                    .map(|(_, output)| output)
+                    .collect();
+
+                Ok(batch)
+            })
+            .collect()
+    }
+
+    /// Craft a transaction used to transfer funds to a contract.
+    pub fn prepare_contract_transfer(
+        to: ContractId,
+        amount: u64,
+        asset_id: AssetId,
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        let script_data: Vec<u8> = [
+            to.to_vec(),
+            amount.to_be_bytes().to_vec(),
+            asset_id.to_vec(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        // This script loads:
+        //  - a pointer to the contract id,
+        //  - the actual amount
+        //  - a pointer to the asset id
+        // into the registers 0x10, 0x12, 0x13
+        // and calls the TR instruction
+        let script = vec![
+            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
+            op::addi(0x11, 0x10, ContractId::LEN as u16),
+            op::lw(0x12, 0x11, 0),
+            op::addi(0x13, 0x11, WORD_SIZE as u16),
+            op::tr(0x10, 0x12, 0x13),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect();
+
+        ScriptTransactionBuilder::new(network_info)
+            .with_script(script)
+            .with_script_data(script_data)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_tx_policies(tx_policies)
+    }
+
+    /// Craft a transaction used to call a single contract method.
+    ///
+    /// The script data is laid out as follows:
+    /// 1. Amount to be forwarded `(1 * `[`WORD_SIZE`]`)`
+    /// 2. Asset ID to be forwarded ([`AssetId::LEN`])
+    /// 3. Gas to be forwarded `(1 * `[`WORD_SIZE`]`)`
+    /// 4. Contract ID ([`ContractId::LEN`])
+    /// 5. Function selector `(1 * `[`WORD_SIZE`]`)`
+    /// 6. Pointer to the call data `(1 * `[`WORD_SIZE`]`)`
+    /// 7. Call data (variable length)
+    ///
+    /// Items 4. to 6. form the call frame expected by the `CALL` instruction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prepare_contract_call(
+        contract_id: ContractId,
+        fn_selector: Selector,
+        call_data: Vec<u8>,
+        amount: u64,
+        asset_id: AssetId,
+        gas_forwarded: Option<u64>,
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        // This script loads:
+        //  - the amount
+        //  - a pointer to the asset id
+        //  - the gas to be forwarded (or `$cgas` if not set)
+        //  - a pointer to the call frame starting with the contract id
+        // into the registers 0x11, 0x12, 0x13, 0x14
+        // and calls the CALL instruction
+        let call_instructions = match gas_forwarded {
+            Some(_) => vec![op::lw(0x13, 0x13, 0), op::call(0x14, 0x11, 0x12, 0x13)],
+            None => vec![op::call(0x14, 0x11, 0x12, RegId::CGAS)],
+        };
+        let script: Vec<u8> = [
+            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
+            op::lw(0x11, 0x10, 0),
+            op::addi(0x12, 0x10, WORD_SIZE as u16),
+            op::addi(0x13, 0x12, AssetId::LEN as u16),
+            op::addi(0x14, 0x13, WORD_SIZE as u16),
+        ]
+        .into_iter()
+        .chain(call_instructions)
+        .chain([op::ret(RegId::ONE)])
+        .collect();
+
+        let script_data_offset = offsets::base_offset_script(&network_info.consensus_parameters)
+            + padded_len_usize(script.len());
+        let call_data_ptr = script_data_offset
+            + WORD_SIZE // amount
+            + AssetId::LEN
+            + WORD_SIZE // gas forwarded
+            + ContractId::LEN
+            + WORD_SIZE // fn selector
+            + WORD_SIZE; // call data pointer
+
+        let script_data: Vec<u8> = [
+            amount.to_be_bytes().to_vec(),
+            asset_id.to_vec(),
+            gas_forwarded.unwrap_or_default().to_be_bytes().to_vec(),
+            contract_id.to_vec(),
+            fn_selector.to_vec(),
+            (call_data_ptr as u64).to_be_bytes().to_vec(),
+            call_data,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        ScriptTransactionBuilder::new(network_info)
+            .with_script(script)
+            .with_script_data(script_data)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+            .with_tx_policies(tx_policies)
+    }
+
+    /// Craft a transaction used to transfer funds to the base chain.
+    /// Sends a message with `amount` to `to`. The base asset change is also
+    /// returned to `to`, use `prepare_message_to_output_with_change` to return it
+    /// to the funder instead.
+    pub fn prepare_message_to_output(
+        to: Address,
+        amount: u64,
+        inputs: Vec<Input>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        Self::prepare_message_to_output_with_change(
+            to,
+            to,
+            amount,
+            inputs,
+            tx_policies,
+            network_info,
+        )
+    }
+
+    /// Like `prepare_message_to_output` but errors early if the base asset inputs
+    /// can not cover `amount` plus the estimated fee. The fee is estimated offline
+    /// from the size of the transaction, the gas used by the script is only included
+    /// if the `script_gas_limit` is set in `tx_policies`.
+    pub fn try_prepare_message_to_output(
+        to: Address,
+        amount: u64,
+        inputs: Vec<Input>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Result<Self> {
+        let tb = Self::prepare_message_to_output(to, amount, inputs, tx_policies, network_info);
+
+        // Signatures are added after this call, so the inputs are estimated separately
+        let inputs_fee = checked_sum(
+            tb.inputs
+                .iter()
+                .map(|input| tb.marginal_input_fee(input))
+                .collect::<Result<Vec<_>>>()?,
+        )?;
+        let tx_fee = tb
+            .clone()
+            .with_inputs(vec![])
+            .fee_at_gas_price(tb.effective_gas_price(), tb.gas_limit.unwrap_or_default())?
+            .max_fee();
+        let estimated_fee = checked_sum([inputs_fee, tx_fee])?;
+
+        let available = tb.base_asset_input_amount()?;
+        if available < checked_sum([amount, estimated_fee])? {
+            return Err(error!(
+                InvalidData,
+                "base asset inputs hold `{available}` which does not cover the message amount `{amount}` and the estimated fee `{estimated_fee}`"
+            ));
+        }
+
+        Ok(tb)
+    }
+
+    /// Sends a message with `amount` to `to` and returns the base asset change to
+    /// `change_recipient`.
+    pub fn prepare_message_to_output_with_change(
+        to: Address,
+        change_recipient: Address,
+        amount: u64,
+        inputs: Vec<Input>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        let script_data: Vec<u8> = [to.to_vec(), amount.to_be_bytes().to_vec()]
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // This script loads:
+        //  - a pointer to the recipient address,
+        //  - the amount
+        // into the registers 0x10, 0x11
+        // and calls the SMO instruction
+        let script: Vec<u8> = vec![
+            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
+            op::addi(0x11, 0x10, Bytes32::LEN as u16),
+            op::lw(0x11, 0x11, 0),
+            op::smo(0x10, 0x00, 0x00, 0x11),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect();
+
+        let outputs = vec![Output::change(change_recipient, 0, BASE_ASSET_ID)];
+
+        ScriptTransactionBuilder::new(network_info)
+            .with_tx_policies(tx_policies)
+            .with_script(script)
+            .with_script_data(script_data)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+    }
+
+    /// Craft a transaction withdrawing `amount` to `to` on the base layer with a
+    /// message carrying `data`, e.g. the calldata of a base layer contract. The base
+    /// asset change is returned to `change_recipient`.
+    ///
+    /// Once the transaction is committed, its `Receipt::MessageOut` holds what is
+    /// needed downstream: the `nonce` which, together with the tx id and a later
+    /// block, is passed to `Provider::get_message_proof`, and the `sender`,
+    /// `recipient`, `amount` and `data` that the base layer verifies against the
+    /// message `digest` when relaying it.
+    ///
+    /// The script data is laid out as follows:
+    ///
+    /// | recipient (32 bytes) | amount (8 bytes) | data length (8 bytes) | data |
+    pub fn prepare_withdrawal(
+        to: Address,
+        amount: u64,
+        data: Vec<u8>,
+        change_recipient: Address,
+        inputs: Vec<Input>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        let script_data: Vec<u8> = [
+            to.to_vec(),
+            amount.to_be_bytes().to_vec(),
+            (data.len() as u64).to_be_bytes().to_vec(),
+            data,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        // This script loads:
+        //  - a pointer to the recipient address,
+        //  - the amount,
+        //  - the data length,
+        //  - a pointer to the data
+        // into the registers 0x10, 0x12, 0x13, 0x14
+        // and calls the SMO instruction
+        let script: Vec<u8> = vec![
+            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
+            op::addi(0x11, 0x10, Address::LEN as u16),
+            op::lw(0x12, 0x11, 0),
+            op::lw(0x13, 0x11, 1),
+            op::addi(0x14, 0x11, 2 * WORD_SIZE as u16),
+            op::smo(0x10, 0x14, 0x13, 0x12),
+            op::ret(RegId::ONE),
+        ]
+        .into_iter()
+        .collect();
+
+        let outputs = vec![Output::change(change_recipient, 0, BASE_ASSET_ID)];
+
+        ScriptTransactionBuilder::new(network_info)
+            .with_tx_policies(tx_policies)
+            .with_script(script)
+            .with_script_data(script_data)
+            .with_inputs(inputs)
+            .with_outputs(outputs)
+    }
+
+    /// Registers a signer that signs the transaction id outside of the SDK.
+    /// Its witness is placed after the ones produced by `add_unresolved_signature`.
+    pub fn add_external_signer(&mut self, signer: impl ExternalSigner + 'static) {
+        self.unresolved_signatures
+            .external_signers
+            .push(Arc::new(signer));
+    }
+
+    /// Removes byte-identical witnesses keeping the first occurrence. Signed inputs
+    /// reference witnesses placed after the user provided ones, so their indexes are
+    /// resolved against the shrunk witness vector. Indexes already handed out, e.g.
+    /// by `add_witness`, are not updated: call it before handing them out or remap
+    /// them with the returned indexes, the witness at `i` moves to `returned[i]`.
+    pub fn dedup_witnesses(&mut self) -> Vec<usize> {
+        dedup_witnesses(&mut self.witnesses)
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets what happens when the `script_gas_limit` is higher than the max gas per
+    /// tx. Defaults to `OverLimitBehavior::Error`.
+    pub fn with_over_limit_behavior(mut self, behavior: OverLimitBehavior) -> Self {
+        self.over_limit_behavior = behavior;
+        self
+    }
+
+    /// Runs `hook` on the `Script` once its witnesses are final, before it is
+    /// wrapped in a `ScriptTransaction`, e.g. to set a field the builder doesn't
+    /// expose. Changing anything but the witnesses invalidates the signatures.
+    pub fn with_post_build_hook(
+        mut self,
+        hook: impl Fn(&mut Script) + Send + Sync + 'static,
+    ) -> Self {
+        self.post_build_hook = Some(PostBuildHook(Arc::new(hook)));
+        self
+    }
+
+    fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
+        self.gas_limit = tx_policies.script_gas_limit();
+        self.gas_price = tx_policies.gas_price();
+        self.witness_limit = tx_policies.witness_limit();
+        self.max_fee = tx_policies.max_fee();
+        self.maturity = tx_policies.maturity();
+
+        self
+    }
+}
+
+/// A resolved `Script` that still holds the dry run witnesses. The signatures can
+/// only be generated once the `script_gas_limit` is final.
+#[derive(Clone)]
+struct UnsignedScript {
+    tx: Script,
+    is_using_predicates: bool,
+    witnesses: Vec<Witness>,
+    unresolved_signatures: UnresolvedSignatures,
+    post_build_hook: Option<PostBuildHook<Script>>,
+}
+
+impl UnsignedScript {
+    fn sign(self, chain_id: &ChainId, external_witnesses: Vec<Witness>) -> ScriptTransaction {
+        let mut tx = self.tx;
+
+        let missing_witnesses =
+            generate_missing_witnesses(tx.id(chain_id), &self.unresolved_signatures);
+        *tx.witnesses_mut() = [self.witnesses, missing_witnesses, external_witnesses].concat();
+        if let Some(PostBuildHook(hook)) = &self.post_build_hook {
+            hook(&mut tx);
+        }
+
+        ScriptTransaction {
+            tx,
+            is_using_predicates: self.is_using_predicates,
+        }
+    }
+
+    async fn sign_with_external_signers(self, chain_id: &ChainId) -> Result<ScriptTransaction> {
+        let external_witnesses = generate_external_witnesses(
+            self.tx.id(chain_id),
+            &self.unresolved_signatures.external_signers,
+        )
+        .await?;
+
+        Ok(self.sign(chain_id, external_witnesses))
+    }
+}
+
+impl CreateTransactionBuilder {
+    fn new(network_info: NetworkInfo) -> CreateTransactionBuilder {
+        CreateTransactionBuilder {
+            gas_price: None,
+            witness_limit: None,
+            max_fee: None,
+            maturity: 0,
+            bytecode_length: 0,
+            bytecode_witness_index: 0,
+            storage_slots: vec![],
+            salt: Default::default(),
+            inputs: vec![],
+            outputs: vec![],
+            witnesses: vec![],
+            raw_policies: None,
+            manual_witnesses: false,
+            gas_costs_override: None,
+            max_size: None,
+            tx_pointer: TxPointer::default(),
+            required_signers: None,
+            network_info,
+            unresolved_signatures: Default::default(),
+            fee_calculator: Arc::new(DefaultFeeCalculator),
+            post_build_hook: None,
+        }
+    }
+
+    /// `CreateTransaction`s have no script so they never dry run.
+    pub fn requires_dry_run(&self) -> bool {
+        false
+    }
+
+    /// `CreateTransaction`s never require a dry run. Building offline only
+    /// additionally checks that every predicate input provides its `predicate_gas_used`.
+    pub fn build_offline(self) -> Result<CreateTransaction> {
+        validate_predicates_gas_used(&self.inputs)?;
+
+        self.build()
+    }
+
+    /// `CreateTransaction`s have no `script_gas_limit` so `gas_used` is ignored.
+    #[cfg(any(test, feature = "test-helpers"))]
+    pub fn build_deterministic(self, _: u64) -> Result<CreateTransaction> {
+        self.build_offline()
+    }
+
+    pub fn build(self) -> Result<CreateTransaction> {
+        let mut tx = self.resolve_fuel_tx()?;
+
+        if !self.manual_witnesses {
+            let missing_witnesses = generate_missing_witnesses(
+                tx.id(&self.network_info.chain_id()),
+                &self.unresolved_signatures,
+            );
+            tx.witnesses_mut().extend(missing_witnesses);
+        }
+        if let Some(PostBuildHook(hook)) = &self.post_build_hook {
+            hook(&mut tx);
+        }
+        validate_tx_size(tx.size(), self.max_size)?;
+
+        Ok(CreateTransaction {
+            tx,
+            is_using_predicates: self.is_using_predicates(),
+        })
+    }
+
+    fn validate_bytecode_witness_index(&self) -> std::result::Result<(), BuildError> {
+        let index = self.bytecode_witness_index;
+
+        if index as usize >= self.witnesses.len() {
+            return Err(BuildError::BytecodeWitnessOutOfRange {
+                index,
+                num_witnesses: self.witnesses.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // An empty binary used to build, so it is only reported by `try_build`
+    fn validate_bytecode_witness(&self) -> std::result::Result<(), BuildError> {
+        self.validate_bytecode_witness_index()?;
+
+        let index = self.bytecode_witness_index;
+        if self.witnesses[index as usize].as_vec().is_empty() {
+            return Err(BuildError::EmptyBytecodeWitness { index });
+        }
+
+        Ok(())
+    }
+
+    // Borrows the builder, cloning only what ends up in the tx
+    fn resolve_fuel_tx(&self) -> Result<Create> {
+        self.validate()?;
+        self.validate_bytecode_witness_index()
+            .map_err(|err| error!(InvalidData, "{err}"))?;
+
+        Ok(FuelTransaction::create(
+            self.bytecode_witness_index,
+            self.generate_fuel_policies(),
+            self.salt,
+            self.storage_slots.clone(),
+            resolve_fuel_inputs(
+                self.inputs.iter().cloned(),
+                self.inputs_offset(),
+                self.num_witnesses()?,
+                &self.unresolved_signatures,
+                self.tx_pointer,
+            )?,
+            self.outputs.clone(),
+            self.witnesses.clone(),
+        ))
+    }
+
+    // `CreateTransaction`s never dry run, the provider is only taken to mirror
+    // the `ScriptTransactionBuilder`
+    async fn resolve_fee_estimation_tx(&self, _: impl DryRunner) -> Result<CreateTransaction> {
+        Ok(CreateTransaction {
+            tx: self.resolve_fuel_tx_for_fee(0)?,
+            is_using_predicates: self.is_using_predicates(),
+        })
+    }
+
+    // Like `build` but without signing or cloning the builder
+    fn resolve_fuel_tx_for_fee(&self, _: u64) -> Result<Create> {
+        let mut tx = self.resolve_fuel_tx()?;
+        *tx.witnesses_mut() = self.fee_estimation_witnesses();
+
+        Ok(tx)
+    }
+
+    fn generate_fuel_policies(&self) -> Policies {
+        let mut policies = self.generate_shared_fuel_policies();
+        policies.set(
+            PolicyType::WitnessLimit,
+            self.witness_limit.or(Some(DEFAULT_CREATE_WITNESS_LIMIT)),
+        );
+        self.apply_raw_policies(&mut policies);
+
+        policies
+    }
+
+    fn base_offset(&self) -> usize {
+        offsets::base_offset_create(self.consensus_parameters())
+    }
+
+    // Offset at which the first input starts
+    fn inputs_offset(&self) -> usize {
+        self.base_offset()
+            + self.storage_slots.len() * StorageSlot::SLOT_SIZE
+            + self.generate_fuel_policies().size_dynamic()
+    }
+
+    // The id does not depend on the witnesses
+    fn preview_tx_id(&self, chain_id: &ChainId) -> Result<Bytes32> {
+        Ok(self.resolve_fuel_tx()?.id(chain_id))
+    }
+
+    /// Removes byte-identical witnesses keeping the first occurrence. The
+    /// `bytecode_witness_index` is updated to point at the surviving witness.
+    pub fn dedup_witnesses(&mut self) {
+        let new_indexes = dedup_witnesses(&mut self.witnesses);
+
+        if let Some(&new_idx) = new_indexes.get(self.bytecode_witness_index as usize) {
+            self.bytecode_witness_index = new_idx as u8;
+        }
+    }
+
+    pub fn with_bytecode_length(mut self, bytecode_length: u64) -> Self {
+        self.bytecode_length = bytecode_length;
+        self
+    }
+
+    /// Index the contract binary will have if it is added as the next witness. The
+    /// signature witnesses are appended after the user witnesses during build, so
+    /// signers added before or after the binary don't shift it.
+    pub fn bytecode_witness_position(&self) -> u8 {
+        self.witnesses.len() as u8
+    }
+
+    pub fn with_bytecode_witness_index(mut self, bytecode_witness_index: u8) -> Self {
+        self.bytecode_witness_index = bytecode_witness_index;
+        self
+    }
+
+    pub fn with_storage_slots(mut self, mut storage_slots: Vec<StorageSlot>) -> Self {
+        // Storage slots have to be sorted otherwise we'd get a `TransactionCreateStorageSlotOrder`
+        // error.
+        storage_slots.sort();
+        self.storage_slots = storage_slots;
+        self
+    }
+
+    pub fn with_salt(mut self, salt: impl Into<Salt>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Runs `hook` on the `Create` once its witnesses are final, before it is
+    /// wrapped in a `CreateTransaction`. Changing anything but the witnesses
+    /// invalidates the signatures.
+    pub fn with_post_build_hook(
+        mut self,
+        hook: impl Fn(&mut Create) + Send + Sync + 'static,
+    ) -> Self {
+        self.post_build_hook = Some(PostBuildHook(Arc::new(hook)));
+        self
+    }
+
+    /// Computes the initial state root of a contract holding `storage_slots`, i.e. the
+    /// root of the sparse merkle tree the slots are inserted into. The order of the
+    /// slots doesn't matter.
+    pub fn compute_state_root(storage_slots: &[StorageSlot]) -> Bytes32 {
+        FuelContract::initial_state_root(storage_slots.iter())
+    }
+
+    pub fn prepare_contract_deployment(
+        binary: Vec<u8>,
+        contract_id: ContractId,
+        state_root: Bytes32,
+        salt: Salt,
+        storage_slots: Vec<StorageSlot>,
+        tx_policies: TxPolicies,
+        network_info: NetworkInfo,
+    ) -> Self {
+        let bytecode_witness_index = 0;
+        let outputs = vec![Output::contract_created(contract_id, state_root)];
+        let witnesses = vec![binary.into()];
+
+        CreateTransactionBuilder::new(network_info)
+            .with_tx_policies(tx_policies)
+            .with_bytecode_witness_index(bytecode_witness_index)
+            .with_salt(salt)
+            .with_storage_slots(storage_slots)
+            .with_outputs(outputs)
+            .with_witnesses(witnesses)
+    }
+
+    fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
+        self.gas_price = tx_policies.gas_price();
+        self.witness_limit = tx_policies.witness_limit();
+        self.max_fee = tx_policies.max_fee();
+        self.maturity = tx_policies.maturity();
+
+        self
+    }
+}
+
+/// Removes duplicated witnesses and returns, for every original index, the index of
+/// the witness that survived.
+fn dedup_witnesses(witnesses: &mut Vec<Witness>) -> Vec<usize> {
+    let mut unique: Vec<Witness> = vec![];
+
+    let new_indexes = witnesses
+        .drain(..)
+        .map(|witness| {
+            unique
+                .iter()
+                .position(|unique_witness| *unique_witness == witness)
+                .unwrap_or_else(|| {
+                    unique.push(witness);
+                    unique.len() - 1
+                })
+        })
+        .collect();
+
+    *witnesses = unique;
+
+    new_indexes
+}
+
+/// Resolve SDK Inputs to fuel_tx Inputs. This function will calculate the right
+/// data offsets for predicates and set witness indexes for signed coins.
+fn resolve_fuel_inputs(
+    inputs: impl IntoIterator<Item = Input>,
+    data_offset: usize,
+    num_witnesses: u8,
+    unresolved_signatures: &UnresolvedSignatures,
+    tx_pointer: TxPointer,
+) -> Result<Vec<FuelInput>> {
+    resolve_fuel_inputs_with_predicate_data_offsets(
+        inputs,
+        data_offset,
+        num_witnesses,
+        unresolved_signatures,
+        tx_pointer,
+    )
+    .map(|inputs| inputs.into_iter().map(|(input, _)| input).collect())
+}
+
+// Also returns the absolute offset of the data of every predicate input
+fn resolve_fuel_inputs_with_predicate_data_offsets(
+    inputs: impl IntoIterator<Item = Input>,
+    mut data_offset: usize,
+    num_witnesses: u8,
+    unresolved_signatures: &UnresolvedSignatures,
+    tx_pointer: TxPointer,
+) -> Result<Vec<(FuelInput, Option<usize>)>> {
+    let resolved_inputs = inputs
+        .into_iter()
+        .map(|input| match input {
+            Input::ResourceSigned { resource } => resolve_signed_resource(
+                resource,
+                &mut data_offset,
+                num_witnesses,
+                unresolved_signatures,
+                tx_pointer,
+            )
+            .map(|input| (input, None)),
+            Input::ResourcePredicate {
+                resource,
+                code,
+                data,
+                predicate_gas_used,
+            } => resolve_predicate_resource(
+                resource,
+                code,
+                data,
+                predicate_gas_used,
+                &mut data_offset,
+                tx_pointer,
+            )
+            .map(|(input, predicate_data_offset)| (input, Some(predicate_data_offset))),
+            Input::Contract {
+                utxo_id,
+                balance_root,
+                state_root,
+                tx_pointer,
+                contract_id,
+            } => {
+                data_offset += offsets::contract_input_offset();
+                Ok((
+                    FuelInput::contract(utxo_id, balance_root, state_root, tx_pointer, contract_id),
+                    None,
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let total_witnesses = num_witnesses as usize + unresolved_signatures.len();
+    validate_witness_indexes(
+        resolved_inputs.iter().map(|(input, _)| input),
+        total_witnesses,
+    )?;
+
+    Ok(resolved_inputs)
+}
+
+// Catches a miscomputed witness index offset before the tx is submitted
+fn validate_witness_indexes<'a>(
+    inputs: impl IntoIterator<Item = &'a FuelInput>,
+    total_witnesses: usize,
+) -> Result<()> {
+    for (input_idx, input) in inputs.into_iter().enumerate() {
+        match input.witness_index() {
+            Some(witness_idx) if witness_idx as usize >= total_witnesses => {
+                return Err(error!(
+                    InvalidData,
+                    "input at index `{input_idx}` references witness `{witness_idx}` but the tx only has `{total_witnesses}` witnesses"
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_signed_resource(
+    resource: CoinType,
+    data_offset: &mut usize,
+    num_witnesses: u8,
+    unresolved_signatures: &UnresolvedSignatures,
+    tx_pointer: TxPointer,
+) -> Result<FuelInput> {
+    match resource {
+        CoinType::Coin(coin) => {
+            *data_offset += offsets::coin_signed_data_offset();
+            let owner = &coin.owner;
+
+            unresolved_signatures
+                .witness_idx_offset(owner)
+                .ok_or(error!(
+                    InvalidData,
+                    "signature missing for coin with owner: `{owner:?}`"
+                ))
+                .map(|witness_idx_offset| {
+                    create_coin_input_with_tx_pointer(
+                        coin,
+                        num_witnesses + witness_idx_offset as u8,
+                        tx_pointer,
+                    )
+                })
+        }
+        CoinType::Message(message) => {
+            *data_offset += offsets::message_signed_data_offset(message.data.len());
+            let recipient = &message.recipient;
+
+            unresolved_signatures
+                .witness_idx_offset(recipient)
+                .ok_or(error!(
+                    InvalidData,
+                    "signature missing for message with recipient: `{recipient:?}`"
+                ))
+                .map(|witness_idx_offset| {
+                    create_coin_message_input(message, num_witnesses + witness_idx_offset as u8)
+                })
+        }
+    }
+}
+
+fn resolve_predicate_resource(
+    resource: CoinType,
+    code: Vec<u8>,
+    data: UnresolvedBytes,
+    predicate_gas_used: Option<u64>,
+    data_offset: &mut usize,
+    tx_pointer: TxPointer,
+) -> Result<(FuelInput, usize)> {
+    let (mut input, predicate_data_offset) = match resource {
+        CoinType::Coin(coin) => {
+            *data_offset += offsets::coin_predicate_data_offset(code.len());
+            let predicate_data_offset = *data_offset;
+
+            let data = data.resolve(*data_offset as u64);
+            *data_offset += data.len();
+
+            let asset_id = coin.asset_id;
+            (
+                create_coin_predicate_with_tx_pointer(coin, asset_id, code, data, tx_pointer),
+                predicate_data_offset,
+            )
+        }
+        CoinType::Message(message) => {
+            *data_offset += offsets::message_predicate_data_offset(message.data.len(), code.len());
+            let predicate_data_offset = *data_offset;
+
+            let data = data.resolve(*data_offset as u64);
+            *data_offset += data.len();
+
+            (
+                create_coin_message_predicate(message, code, data),
+                predicate_data_offset,
+            )
+        }
+    };
+
+    if let Some(gas_used) = predicate_gas_used {
+        match &mut input {
+            FuelInput::CoinPredicate(CoinPredicate {
+                predicate_gas_used, ..
+            })
+            | FuelInput::MessageCoinPredicate(MessageCoinPredicate {
+                predicate_gas_used, ..
+            })
+            | FuelInput::MessageDataPredicate(MessageDataPredicate {
+                predicate_gas_used, ..
+            }) => *predicate_gas_used = gas_used,
+            _ => {}
+        }
+    }
+
+    Ok((input, predicate_data_offset))
+}
+
+// Inverse of `resolve_fuel_inputs`, the predicate data is kept as it was resolved
+fn unresolve_fuel_input(input: FuelInput) -> Input {
+    let resolved_data = |data| UnresolvedBytes::new(vec![Data::Inline(data)]);
+
+    match input {
+        FuelInput::CoinSigned(coin) => Input::resource_signed(CoinType::Coin(Coin {
+            amount: coin.amount,
+            asset_id: coin.asset_id,
+            utxo_id: coin.utxo_id,
+            owner: coin.owner.into(),
+            ..Default::default()
+        })),
+        FuelInput::CoinPredicate(coin) => Input::resource_predicate(
+            CoinType::Coin(Coin {
+                amount: coin.amount,
+                asset_id: coin.asset_id,
+                utxo_id: coin.utxo_id,
+                owner: coin.owner.into(),
+                ..Default::default()
+            }),
+            coin.predicate,
+            resolved_data(coin.predicate_data),
+        )
+        .with_predicate_gas_used(coin.predicate_gas_used),
+        FuelInput::MessageCoinSigned(message) => {
+            Input::resource_signed(CoinType::Message(Message::new(
+                message.sender.into(),
+                message.recipient.into(),
+                message.nonce,
+                message.amount,
+                vec![],
+            )))
+        }
+        FuelInput::MessageDataSigned(message) => {
+            Input::resource_signed(CoinType::Message(Message::new(
+                message.sender.into(),
+                message.recipient.into(),
+                message.nonce,
+                message.amount,
+                message.data,
+            )))
+        }
+        FuelInput::MessageCoinPredicate(message) => Input::resource_predicate(
+            CoinType::Message(Message::new(
+                message.sender.into(),
+                message.recipient.into(),
+                message.nonce,
+                message.amount,
+                vec![],
+            )),
+            message.predicate,
+            resolved_data(message.predicate_data),
+        )
+        .with_predicate_gas_used(message.predicate_gas_used),
+        FuelInput::MessageDataPredicate(message) => Input::resource_predicate(
+            CoinType::Message(Message::new(
+                message.sender.into(),
+                message.recipient.into(),
+                message.nonce,
+                message.amount,
+                message.data,
+            )),
+            message.predicate,
+            resolved_data(message.predicate_data),
+        )
+        .with_predicate_gas_used(message.predicate_gas_used),
+        FuelInput::Contract(contract) => Input::contract(
+            contract.utxo_id,
+            contract.balance_root,
+            contract.state_root,
+            contract.tx_pointer,
+            contract.contract_id,
+        ),
+    }
+}
+
+// Returns the items only in `new` and the items only in `old`, an item present
+// several times has to be matched as many times
+fn diff_items<T: Clone + PartialEq>(old: &[T], new: &[T]) -> (Vec<T>, Vec<T>) {
+    let mut removed = old.to_vec();
+    let mut added = vec![];
+
+    for item in new {
+        match removed.iter().position(|old_item| old_item == item) {
+            Some(idx) => {
+                removed.remove(idx);
+            }
+            None => added.push(item.clone()),
+        }
+    }
+
+    (added, removed)
+}
+
+// Errors instead of wrapping so that extreme amounts are never misreported
+fn checked_sum(amounts: impl IntoIterator<Item = u64>) -> Result<u64> {
+    amounts.into_iter().try_fold(0u64, |total, amount| {
+        total
+            .checked_add(amount)
+            .ok_or(error!(InvalidData, "sum of amounts overflows `u64`"))
+    })
+}
+
+fn validate_tx_size(tx_size: usize, max_size: Option<usize>) -> Result<()> {
+    match max_size {
+        Some(max_size) if tx_size > max_size => Err(error!(
+            InvalidData,
+            "transaction is `{tx_size}` bytes long, more than the max size of `{max_size}` bytes"
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn validate_gas_estimation_tolerance(tolerance: f32) -> Result<()> {
+    if !(0.0..=1.0).contains(&tolerance) {
+        return Err(error!(
+            InvalidData,
+            "gas estimation tolerance must be between `0.0` and `1.0`, got: `{tolerance}`"
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_predicates_gas_used(inputs: &[Input]) -> Result<()> {
+    let missing_gas_idx = inputs.iter().position(|input| {
+        matches!(
+            input,
+            Input::ResourcePredicate {
+                predicate_gas_used: None,
+                ..
+            }
+        )
+    });
+
+    match missing_gas_idx {
+        Some(idx) => Err(error!(
+            InvalidData,
+            "predicate input at index `{idx}` does not provide its `predicate_gas_used`"
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn create_coin_input(coin: Coin, witness_index: u8) -> FuelInput {
+    create_coin_input_with_tx_pointer(coin, witness_index, TxPointer::default())
+}
+
+pub fn create_coin_input_with_tx_pointer(
+    coin: Coin,
+    witness_index: u8,
+    tx_pointer: TxPointer,
+) -> FuelInput {
+    FuelInput::coin_signed(
+        coin.utxo_id,
+        coin.owner.into(),
+        coin.amount,
+        coin.asset_id,
+        tx_pointer,
+        witness_index,
+        0u32.into(),
+    )
+}
+
+pub fn create_coin_message_input(message: Message, witness_index: u8) -> FuelInput {
+    if message.data.is_empty() {
+        FuelInput::message_coin_signed(
+            message.sender.into(),
+            message.recipient.into(),
+            message.amount,
+            message.nonce,
+            witness_index,
+        )
+    } else {
+        FuelInput::message_data_signed(
+            message.sender.into(),
+            message.recipient.into(),
+            message.amount,
+            message.nonce,
+            witness_index,
+            message.data,
+        )
+    }
+}
+
+pub fn create_coin_predicate(
+    coin: Coin,
+    asset_id: AssetId,
+    code: Vec<u8>,
+    predicate_data: Vec<u8>,
+) -> FuelInput {
+    create_coin_predicate_with_tx_pointer(
+        coin,
+        asset_id,
+        code,
+        predicate_data,
+        TxPointer::default(),
+    )
+}
+
+pub fn create_coin_predicate_with_tx_pointer(
+    coin: Coin,
+    asset_id: AssetId,
+    code: Vec<u8>,
+    predicate_data: Vec<u8>,
+    tx_pointer: TxPointer,
+) -> FuelInput {
+    FuelInput::coin_predicate(
+        coin.utxo_id,
+        coin.owner.into(),
+        coin.amount,
+        asset_id,
+        tx_pointer,
+        0u32.into(),
+        0u64,
+        code,
+        predicate_data,
+    )
+}
+
+pub fn create_coin_message_predicate(
+    message: Message,
+    code: Vec<u8>,
+    predicate_data: Vec<u8>,
+) -> FuelInput {
+    if message.data.is_empty() {
+        FuelInput::message_coin_predicate(
+            message.sender.into(),
+            message.recipient.into(),
+            message.amount,
+            message.nonce,
+            0u64,
+            code,
+            predicate_data,
+        )
+    } else {
+        FuelInput::message_data_predicate(
+            message.sender.into(),
+            message.recipient.into(),
+            message.amount,
+            message.nonce,
+            0u64,
+            message.data,
+            code,
+            predicate_data,
+        )
+    }
+}
+
+/// Gas reserved on top of `max_gas` because its fee is rounded up, see `gas_to_fee`.
+/// `ceil(gas * gas_price / gas_price_factor)` covers at most
+/// `(gas_price_factor - 1) / gas_price` units of gas more than `gas`, and none at a
+/// zero `gas_price` since the fee is zero.
+pub fn gas_rounding_margin(gas_price: u64, fee_params: &FeeParameters) -> u64 {
+    fee_params
+        .gas_price_factor
+        .saturating_sub(1)
+        .checked_div(gas_price)
+        .unwrap_or_default()
+}
+
+/// Converts `gas` into a fee the same way `TransactionFee` does, i.e. rounding up.
+fn gas_to_fee(gas: u64, gas_price: u64, fee_params: &FeeParameters) -> Option<u64> {
+    let gas_price_factor = fee_params.gas_price_factor as u128;
+    let fee = (gas as u128)
+        .checked_mul(gas_price as u128)?
+        .checked_add(gas_price_factor.checked_sub(1)?)?
+        / gas_price_factor;
+
+    u64::try_from(fee).ok()
+}
+
+fn generate_missing_witnesses(
+    id: Bytes32,
+    unresolved_signatures: &UnresolvedSignatures,
+) -> Vec<Witness> {
+    unresolved_signatures
+        .secret_keys
+        .iter()
+        .map(|secret_key| {
+            let message = CryptoMessage::from_bytes(*id);
+            let signature = Signature::sign(secret_key, &message);
+
+            Witness::from(signature.as_ref())
+        })
+        .collect()
+}
+
+async fn generate_external_witnesses(
+    id: Bytes32,
+    external_signers: &[Arc<dyn ExternalSigner>],
+) -> Result<Vec<Witness>> {
+    let message = CryptoMessage::from_bytes(*id);
+
+    let mut witnesses = Vec::with_capacity(external_signers.len());
+    for signer in external_signers {
+        let raw_signature = signer.sign_raw(*id).await?;
+        let signature = signer.to_signature(raw_signature)?;
+
+        let recovered = signature
+            .recover(&message)
+            .map_err(|e| error!(InvalidData, "could not recover external signature: {e:?}"))?;
+        if recovered.hash() != signer.address().hash() {
+            return Err(error!(
+                InvalidData,
+                "external signature does not belong to `{}`",
+                signer.address()
+            ));
+        }
+
+        witnesses.push(Witness::from(signature.as_ref()));
+    }
+
+    Ok(witnesses)
+}
+
+/// Checks that the witness at each given index holds a signature of the tx id that
+/// recovers to the expected owner. Meant to catch signing bugs before submitting.
+pub fn verify_signatures(
+    tx: &ScriptTransaction,
+    chain_id: ChainId,
+    expected_owners: &[(u8, Address)],
+) -> Result<()> {
+    let message = CryptoMessage::from_bytes(*tx.id(chain_id));
+
+    for (witness_idx, expected_owner) in expected_owners {
+        let witness = tx
+            .witnesses()
+            .get(*witness_idx as usize)
+            .ok_or(error!(InvalidData, "witness `{witness_idx}` is missing"))?;
+        let bytes = <[u8; Signature::LEN]>::try_from(witness.as_ref()).map_err(|_| {
+            error!(
+                InvalidData,
+                "witness `{witness_idx}` does not hold a signature"
+            )
+        })?;
+
+        let recovered = Signature::from_bytes(bytes)
+            .recover(&message)
+            .map_err(|e| {
+                error!(
+                    InvalidData,
+                    "could not recover witness `{witness_idx}`: {e:?}"
+                )
+            })?;
+        let recovered_owner = Address::from(*recovered.hash());
+        if recovered_owner != *expected_owner {
+            return Err(error!(
+                InvalidData,
+                "witness `{witness_idx}` is signed by `{recovered_owner}` instead of `{expected_owner}`"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `a` and `b` spend a common coin or message, i.e. at most one of them
+/// can be included in a block.
+pub fn transactions_conflict(a: &impl TransactionBuilder, b: &impl TransactionBuilder) -> bool {
+    fn resource_ids(inputs: &[Input]) -> impl Iterator<Item = CoinTypeId> + '_ {
+        inputs.iter().filter_map(|input| match input {
+            Input::ResourceSigned { resource } | Input::ResourcePredicate { resource, .. } => {
+                Some(resource.id())
+            }
+            Input::Contract { .. } => None,
+        })
+    }
+
+    let spent_by_a: HashSet<CoinTypeId> = resource_ids(a.inputs()).collect();
+
+    resource_ids(b.inputs()).any(|id| spent_by_a.contains(&id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use fuel_asm::Opcode;
+    use fuel_tx::{
+        field::{Policies as PoliciesField, ScriptGasLimit},
+        FormatValidityChecks, ScriptExecutionResult, TxParameters, ValidityError,
+    };
+
+    use super::*;
+    use crate::types::{bech32::Bech32Address, errors::Error, unresolved_bytes::Data, Nonce};
+
+    #[test]
+    fn storage_slots_are_sorted_when_set() {
+        let unsorted_storage_slots = [2, 1].map(given_a_storage_slot).to_vec();
+        let sorted_storage_slots = [1, 2].map(given_a_storage_slot).to_vec();
+
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: Default::default(),
+        };
+        let builder =
+            CreateTransactionBuilder::new(network_info).with_storage_slots(unsorted_storage_slots);
+
+        assert_eq!(builder.storage_slots, sorted_storage_slots);
+    }
+
+    fn given_a_storage_slot(key: u8) -> StorageSlot {
+        let mut bytes_32 = Bytes32::zeroed();
+        bytes_32[0] = key;
+
+        StorageSlot::new(bytes_32, Default::default())
+    }
+
+    #[test]
+    fn state_root_is_computed_from_the_storage_slots() {
+        assert_eq!(
+            CreateTransactionBuilder::compute_state_root(&[]),
+            Bytes32::zeroed()
+        );
+
+        let slot = given_a_storage_slot(1);
+        // A tree with a single leaf has the leaf hash as its root:
+        // sha256(LEAF_PREFIX ++ sha256(key) ++ sha256(value))
+        let expected_root = Hasher::default()
+            .chain([0u8])
+            .chain(Hasher::hash(slot.key()))
+            .chain(Hasher::hash(slot.value()))
+            .finalize();
+        assert_eq!(
+            CreateTransactionBuilder::compute_state_root(&[slot]),
+            expected_root
+        );
+
+        let slots = [1, 2].map(given_a_storage_slot);
+        let reversed = [2, 1].map(given_a_storage_slot);
+        assert_eq!(
+            CreateTransactionBuilder::compute_state_root(&slots),
+            CreateTransactionBuilder::compute_state_root(&reversed)
+        );
+    }
+
+    #[test]
+    fn message_predicate_is_resolved_to_a_message_predicate_input() -> Result<()> {
+        let code = vec![1u8; 8];
+        let data = UnresolvedBytes::new(vec![Data::Inline(vec![2u8; 8])]);
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .add_message_predicate(given_a_message(vec![]), code.clone(), data.clone())
+            .add_message_predicate(given_a_message(vec![3u8; 4]), code.clone(), data);
+
+        let tx = tb.resolve_fuel_tx()?;
+
+        assert!(matches!(tx.inputs()[0], FuelInput::MessageCoinPredicate(_)));
+        assert!(matches!(tx.inputs()[1], FuelInput::MessageDataPredicate(_)));
+        for input in tx.inputs() {
+            let (predicate, predicate_data, _) = input.predicate().expect("is a predicate");
+            assert_eq!(predicate, code.as_slice());
+            assert_eq!(predicate_data, [2u8; 8].as_slice());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn contract_transfer_script_is_disassembled() -> Result<()> {
+        let tb = ScriptTransactionBuilder::prepare_contract_transfer(
+            ContractId::zeroed(),
+            100,
+            BASE_ASSET_ID,
+            vec![],
+            vec![],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+
+        let instructions = tb.disassemble_script()?;
+
+        let mnemonics: Vec<_> = instructions
+            .iter()
+            .map(|instruction| {
+                instruction
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .next()
+                    .expect("split yields at least one item")
+            })
+            .collect();
+        assert_eq!(mnemonics, ["GTF", "ADDI", "LW", "ADDI", "TR", "RET"]);
+
+        let tb = tb.with_script(vec![0xff; 4]);
+        assert!(tb.disassemble_script().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn contract_call_script_and_script_data_follow_the_call_convention() {
+        let contract_id = ContractId::from([1u8; 32]);
+        let asset_id = AssetId::from([2u8; 32]);
+        let fn_selector = [3u8; 8];
+        let call_data = vec![4u8; 16];
+
+        let builder = ScriptTransactionBuilder::prepare_contract_call(
+            contract_id,
+            fn_selector,
+            call_data.clone(),
+            100,
+            asset_id,
+            Some(5_000),
+            vec![],
+            vec![],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+
+        let opcodes = fuel_asm::from_bytes(builder.script.iter().copied())
+            .map(|instruction| instruction.expect("should be a valid instruction").opcode())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            opcodes,
+            vec![
+                Opcode::GTF,
+                Opcode::LW,
+                Opcode::ADDI,
+                Opcode::ADDI,
+                Opcode::ADDI,
+                Opcode::LW,
+                Opcode::CALL,
+                Opcode::RET
+            ]
+        );
+
+        let script_data_offset = offsets::base_offset_script(&ConsensusParameters::default())
+            + padded_len_usize(builder.script.len());
+        let call_data_ptr = (script_data_offset + 96) as u64;
+        let expected_script_data = [
+            100u64.to_be_bytes().to_vec(),
+            asset_id.to_vec(),
+            5_000u64.to_be_bytes().to_vec(),
+            contract_id.to_vec(),
+            fn_selector.to_vec(),
+            call_data_ptr.to_be_bytes().to_vec(),
+            call_data,
+        ]
+        .concat();
+        assert_eq!(builder.script_data, expected_script_data);
+    }
+
+    #[tokio::test]
+    async fn fee_is_drawn_from_the_sponsor_predicate() -> Result<()> {
+        let user_secret = given_a_secret_key(1);
+        let user = Bech32Address::new("fuel", [1u8; 32]);
+        let sponsor = Bech32Address::new("fuel", [2u8; 32]);
+        let asset_id = AssetId::from([3u8; 32]);
+
+        let user_coin = Coin {
+            amount: 100,
+            asset_id,
+            owner: user.clone(),
+            ..Default::default()
+        };
+        let sponsor_coin = Coin {
+            utxo_id: UtxoId::new([2; 32].into(), 0),
+            amount: 1_000,
+            asset_id: BASE_ASSET_ID,
+            owner: sponsor.clone(),
+            ..Default::default()
+        };
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![Input::resource_signed(CoinType::Coin(user_coin))],
+            vec![
+                Output::coin(Address::from([4u8; 32]), 100, asset_id),
+                Output::change((&user).into(), 0, asset_id),
+            ],
+            TxPolicies::default(),
+            given_network_info(),
+        )
+        .with_fee_sponsor(
+            CoinType::Coin(sponsor_coin),
+            vec![1, 2, 3],
+            UnresolvedBytes::default(),
+        )?;
+        tb.add_unresolved_signature(user, user_secret);
+
+        let tx = tb.build(MockDryRunner::default()).await?;
+
+        assert!(tx.is_using_predicates());
+        let base_inputs = tx
+            .inputs()
+            .iter()
+            .filter(|input| input.asset_id(&BASE_ASSET_ID) == Some(&BASE_ASSET_ID))
+            .collect::<Vec<_>>();
+        assert_eq!(base_inputs.len(), 1);
+        assert!(matches!(base_inputs[0], FuelInput::CoinPredicate(_)));
+        assert_eq!(tx.inputs()[0].witness_index(), Some(0));
+        assert!(tx.outputs().iter().any(|output| matches!(
+            output,
+            Output::Change { to, asset_id, .. } if *to == (&sponsor).into() && *asset_id == BASE_ASSET_ID
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn base_asset_surplus_of_the_signed_inputs_goes_to_the_sponsor() -> Result<()> {
+        let user = Bech32Address::new("fuel", [1u8; 32]);
+        let sponsor = Bech32Address::new("fuel", [2u8; 32]);
+        let user_coin = Coin {
+            amount: 500,
+            owner: user,
+            ..Default::default()
+        };
+        let sponsor_coin = Coin {
+            amount: 1_000,
+            owner: sponsor.clone(),
+            utxo_id: UtxoId::new([1; 32].into(), 0),
+            ..Default::default()
+        };
+
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![Input::resource_signed(CoinType::Coin(user_coin))])
+            .with_fee_sponsor(
+                CoinType::Coin(sponsor_coin),
+                vec![1, 2, 3],
+                UnresolvedBytes::default(),
+            )?;
+
+        assert_eq!(
+            tb.outputs,
+            vec![Output::change((&sponsor).into(), 0, BASE_ASSET_ID)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn fee_sponsor_must_provide_the_base_asset() {
+        let coin = Coin {
+            asset_id: AssetId::from([3u8; 32]),
+            ..Default::default()
+        };
+
+        let result = ScriptTransactionBuilder::new(given_network_info()).with_fee_sponsor(
+            CoinType::Coin(coin),
+            vec![],
+            UnresolvedBytes::default(),
+        );
+
+        assert!(matches!(result, Err(Error::InvalidData(_))));
+    }
+
+    #[test]
+    fn cleared_signatures_are_no_longer_resolved() {
+        let owner = Bech32Address::new("fuel", [1u8; 32]);
+        let mut tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![Witness::default()]);
+        tb.add_unresolved_signature(owner.clone(), given_a_secret_key(1));
+        assert_eq!(tb.witness_index_for(&owner), Some(1));
+
+        tb.clear_signatures();
+
+        assert_eq!(tb.witness_index_for(&owner), None);
+        assert!(tb.unresolved_signatures.secret_keys.is_empty());
+    }
+
+    #[test]
+    fn reset_keeps_the_policies() -> Result<()> {
+        let owner = Bech32Address::new("fuel", [1u8; 32]);
+        let mut tb = given_a_signed_transfer(&owner)
+            .with_witnesses(vec![Witness::default()])
+            .with_tx_policies(
+                TxPolicies::default()
+                    .with_gas_price(2)
+                    .with_maturity(3)
+                    .with_max_fee(1_000),
+            );
+        tb.add_unresolved_signature(owner.clone(), given_a_secret_key(1));
+
+        tb.reset_transaction_data();
+
+        assert!(tb.inputs.is_empty());
+        assert!(tb.outputs.is_empty());
+        assert!(tb.witnesses.is_empty());
+        assert!(tb.unresolved_signatures.secret_keys.is_empty());
+        assert_eq!(tb.witness_index_for(&owner), None);
+
+        let tx = tb.build_offline()?;
+        assert_eq!(tx.gas_price(), 2);
+        assert_eq!(tx.maturity(), 3);
+        assert_eq!(tx.max_fee(), Some(1_000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn consolidation_signs_once_per_owner() -> Result<()> {
+        let secret_keys: Vec<_> = (1..=3).map(given_a_secret_key).collect();
+        let owners: Vec<_> = secret_keys
+            .iter()
+            .map(|secret_key| {
+                Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(secret_key).hash())
+            })
+            .collect();
+        let resources = [0, 1, 2, 0]
+            .map(|owner_idx| {
+                CoinType::Coin(Coin {
+                    amount: 100,
+                    owner: owners[owner_idx].clone(),
+                    ..Default::default()
+                })
+            })
+            .to_vec();
+        let to = Address::new([9; 32]);
+
+        let tx = ScriptTransactionBuilder::prepare_consolidation(
+            resources,
+            to,
+            secret_keys,
+            TxPolicies::default(),
+            given_network_info(),
+        )?
+        .build_offline()?;
+
+        assert_eq!(tx.outputs(), &vec![Output::change(to, 0, BASE_ASSET_ID)]);
+        assert_eq!(tx.witnesses().len(), 3);
+
+        let message = CryptoMessage::from_bytes(*tx.id(given_network_info().chain_id()));
+        let witness_indexes: Vec<_> = tx
+            .inputs()
+            .iter()
+            .map(|input| input.witness_index().expect("input should be signed"))
+            .collect();
+        assert_eq!(witness_indexes, vec![0, 1, 2, 0]);
+
+        for (input_idx, owner_idx) in [0, 1, 2, 0].into_iter().enumerate() {
+            let witness = &tx.witnesses()[witness_indexes[input_idx] as usize];
+            let bytes = <[u8; Signature::LEN]>::try_from(witness.as_ref())
+                .expect("witness should be a signature");
+            let signer = Signature::from_bytes(bytes)
+                .recover(&message)
+                .expect("signature should be valid");
+
+            assert_eq!(signer.hash(), owners[owner_idx].hash());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn manual_witnesses_are_not_extended_with_signatures() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+        let manual_witness = Witness::from(vec![7u8; 64]);
+        let mut tb = given_a_signed_transfer(&owner)
+            .with_witnesses(vec![manual_witness.clone()])
+            .with_manual_witnesses();
+        tb.add_unresolved_signature(owner, secret_key);
+
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.witnesses(), &vec![manual_witness]);
+        assert_eq!(tx.inputs()[0].witness_index(), Some(1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn oversized_transfer_is_split_into_valid_transactions() -> Result<()> {
+        let secret_keys: Vec<_> = (1..=2).map(given_a_secret_key).collect();
+        let owners: Vec<_> = secret_keys
+            .iter()
+            .map(|secret_key| {
+                Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(secret_key).hash())
+            })
+            .collect();
+        let inputs = [0, 0, 1, 1, 0]
+            .map(|owner_idx| {
+                Input::resource_signed(CoinType::Coin(Coin {
+                    amount: 100,
+                    owner: owners[owner_idx].clone(),
+                    ..Default::default()
+                }))
+            })
+            .to_vec();
+        let recipient = Address::new([9; 32]);
+        let outputs = vec![
+            Output::coin(recipient, 50, BASE_ASSET_ID),
+            Output::change(recipient, 0, BASE_ASSET_ID),
+        ];
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: ConsensusParameters {
+                tx_params: TxParameters::default().with_max_inputs(2),
+                ..Default::default()
+            },
+        };
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            inputs,
+            outputs,
+            TxPolicies::default(),
+            network_info,
+        );
+        for (owner, secret_key) in owners.iter().zip(secret_keys) {
+            tb.add_unresolved_signature(owner.clone(), secret_key);
+        }
+
+        let txs = tb
+            .split_by_limits()?
+            .into_iter()
+            .map(ScriptTransactionBuilder::build_offline)
+            .collect::<Result<Vec<_>>>()?;
+
+        let num_inputs: Vec<_> = txs.iter().map(|tx| tx.inputs().len()).collect();
+        assert_eq!(num_inputs, vec![2, 2, 1]);
+        for (idx, tx) in txs.iter().enumerate() {
+            assert_eq!(tx.witnesses().len(), 1);
+            assert!(tx
+                .inputs()
+                .iter()
+                .all(|input| input.witness_index() == Some(0)));
+
+            let expected_outputs = if idx == 0 {
+                vec![
+                    Output::coin(recipient, 50, BASE_ASSET_ID),
+                    Output::change(recipient, 0, BASE_ASSET_ID),
+                ]
+            } else {
+                vec![Output::change(recipient, 0, BASE_ASSET_ID)]
+            };
+            assert_eq!(tx.outputs(), &expected_outputs);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_keeps_contract_outputs_and_funds_coin_outputs() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+        let contract_id = ContractId::new([5; 32]);
+        let inputs = vec![
+            given_a_coin_input(100, owner.clone()),
+            given_a_coin_input(100, owner.clone()),
+            Input::contract(
+                UtxoId::new([2; 32].into(), 0),
+                Bytes32::zeroed(),
+                Bytes32::zeroed(),
+                TxPointer::default(),
+                contract_id,
+            ),
+            given_a_coin_input(300, owner.clone()),
+        ];
+        let recipient = Address::new([9; 32]);
+        let outputs = vec![
+            Output::contract(2, Bytes32::zeroed(), Bytes32::zeroed()),
+            Output::coin(recipient, 150, BASE_ASSET_ID),
+            Output::coin(recipient, 250, BASE_ASSET_ID),
+            Output::change(recipient, 0, BASE_ASSET_ID),
+        ];
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: ConsensusParameters {
+                tx_params: TxParameters::default().with_max_inputs(2),
+                ..Default::default()
+            },
+        };
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            inputs,
+            outputs,
+            TxPolicies::default(),
+            network_info,
+        );
+        tb.add_unresolved_signature(owner, secret_key);
+
+        let txs = tb
+            .clone()
+            .split_by_limits()?
+            .into_iter()
+            .map(ScriptTransactionBuilder::build_offline)
+            .collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(txs.len(), 2);
+        assert_eq!(
+            txs[0].outputs(),
+            &vec![
+                Output::coin(recipient, 150, BASE_ASSET_ID),
+                Output::change(recipient, 0, BASE_ASSET_ID),
+            ]
+        );
+        assert!(matches!(txs[1].inputs()[0], FuelInput::Contract(_)));
+        assert_eq!(
+            txs[1].outputs(),
+            &vec![
+                Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed()),
+                Output::coin(recipient, 250, BASE_ASSET_ID),
+                Output::change(recipient, 0, BASE_ASSET_ID),
+            ]
+        );
+
+        // No single sub-transaction can fund it
+        tb.outputs.push(Output::coin(recipient, 350, BASE_ASSET_ID));
+        let result = tb.split_by_limits();
+        assert!(
+            matches!(result, Err(Error::InvalidData(msg)) if msg.contains("can not be funded"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_respects_max_outputs() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+        let asset_ids = [AssetId::from([1; 32]), AssetId::from([2; 32])];
+        let inputs = asset_ids
+            .iter()
+            .enumerate()
+            .map(|(idx, asset_id)| {
+                Input::resource_signed(CoinType::Coin(Coin {
+                    amount: 100,
+                    asset_id: *asset_id,
+                    utxo_id: UtxoId::new([idx as u8; 32].into(), 0),
+                    owner: owner.clone(),
+                    ..Default::default()
+                }))
+            })
+            .collect();
+        let recipient = Address::new([9; 32]);
+        let outputs = asset_ids
+            .iter()
+            .map(|asset_id| Output::change(recipient, 0, *asset_id))
+            .collect();
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: ConsensusParameters {
+                tx_params: TxParameters::default().with_max_outputs(1),
+                ..Default::default()
+            },
+        };
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            inputs,
+            outputs,
+            TxPolicies::default(),
+            network_info,
+        )
+        .with_witnesses(vec![Witness::from(vec![1u8; 8])]);
+        tb.add_unresolved_signature(owner, secret_key);
+
+        let batches = tb.split_by_limits()?;
+
+        // The custom witness is only kept in the first sub-transaction
+        let num_witnesses: Vec<_> = batches.iter().map(|batch| batch.witnesses.len()).collect();
+        assert_eq!(num_witnesses, vec![1, 0]);
+
+        let batch_outputs: Vec<_> = batches.iter().map(|batch| batch.outputs.clone()).collect();
+        assert_eq!(
+            batch_outputs,
+            asset_ids
+                .iter()
+                .map(|asset_id| vec![Output::change(recipient, 0, *asset_id)])
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    fn given_a_fallback_fee_coin() -> (Coin, SecretKey) {
+        let secret_key = given_a_secret_key(2);
+        let coin = Coin {
+            amount: 500,
+            owner: Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash()),
+            ..Default::default()
+        };
+
+        (coin, secret_key)
+    }
+
+    #[tokio::test]
+    async fn fallback_fee_input_is_spent_when_needed() -> Result<()> {
+        let (coin, secret_key) = given_a_fallback_fee_coin();
+        let tb = given_a_base_asset_transfer(vec![Output::coin(
+            Address::zeroed(),
+            1_000,
+            BASE_ASSET_ID,
+        )])
+        .with_fallback_fee_input(coin.clone(), secret_key);
+
+        let tx = tb.build(MockDryRunner::default()).await?;
+
+        assert_eq!(tx.inputs().len(), 2);
+        assert_eq!(tx.inputs()[1].amount(), Some(coin.amount));
+        assert_eq!(tx.witness_count(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fallback_fee_input_is_not_spent_when_not_needed() -> Result<()> {
+        let (coin, secret_key) = given_a_fallback_fee_coin();
+        let tb =
+            given_a_base_asset_transfer(vec![Output::coin(Address::zeroed(), 100, BASE_ASSET_ID)])
+                .with_fallback_fee_input(coin, secret_key);
+
+        let tx = tb.build(MockDryRunner::default()).await?;
+
+        assert_eq!(tx.inputs().len(), 1);
+        assert_eq!(tx.witness_count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sizes_with_and_without_witnesses_add_up() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_limit(100)
+            .with_witnesses(vec![Witness::from(vec![1u8; 10])]);
+
+        let size_without_witnesses = tb.size_without_witnesses()?;
+        let witness_sizes = tb.witness_sizes();
+        let tx = tb.build_offline()?;
+
+        assert_eq!(witness_sizes.len(), 2);
+        assert_eq!(
+            size_without_witnesses + witness_sizes.iter().sum::<usize>(),
+            tx.tx.size()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resource_spent_as_signed_and_predicate_input_is_rejected() {
+        let coin = Coin {
+            amount: 100,
+            utxo_id: UtxoId::new([1; 32].into(), 0),
+            ..Default::default()
+        };
+        let mut tb = ScriptTransactionBuilder::new(given_network_info()).with_inputs(vec![
+            Input::resource_signed(CoinType::Coin(coin.clone())),
+            Input::resource_predicate(CoinType::Coin(coin), vec![1], Default::default())
+                .with_predicate_gas_used(0),
+        ]);
+        tb.add_unresolved_signature(Bech32Address::default(), given_a_secret_key(1));
+
+        let result = tb.build_offline();
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg.contains("signed input at index `0` and the predicate input at index `1`")
+        ));
+    }
+
+    #[test]
+    fn contract_output_referencing_a_missing_input_is_rejected() {
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![given_a_predicate_input(Some(0))])
+            .with_outputs(vec![Output::contract(
+                1,
+                Bytes32::zeroed(),
+                Bytes32::zeroed(),
+            )]);
+
+        let result = tb.build_offline();
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg == "contract output at index `0` references input `1` but the tx only has `1` inputs"
+        ));
+    }
+
+    #[test]
+    fn outputs_are_reordered_and_contract_references_stay_valid() -> Result<()> {
+        let contract_input = Input::contract(
+            Default::default(),
+            Bytes32::zeroed(),
+            Bytes32::zeroed(),
+            TxPointer::default(),
+            ContractId::zeroed(),
+        );
+        let coin = Output::coin(Address::zeroed(), 10, BASE_ASSET_ID);
+        let contract = Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed());
+        let change = Output::change(Address::zeroed(), 0, BASE_ASSET_ID);
+        let mut tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![contract_input])
+            .with_outputs(vec![coin, contract, change]);
+
+        tb.reorder_outputs(vec![2, 0, 1])?;
+
+        assert_eq!(tb.outputs(), &vec![change, coin, contract]);
+        assert!(tb.validate_contract_outputs().is_ok());
+
+        for invalid_order in [vec![0, 1], vec![0, 0, 1], vec![0, 1, 3]] {
+            assert!(tb.reorder_outputs(invalid_order).is_err());
+        }
+        assert_eq!(tb.outputs(), &vec![change, coin, contract]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn arbitrary_witness_is_placed_at_the_returned_index() -> Result<()> {
+        let proof = vec![7u8; 100];
+        let mut tb =
+            given_a_base_asset_transfer(vec![]).with_witnesses(vec![Witness::from(vec![1u8; 8])]);
+
+        let witness_idx = tb.add_witness(proof.clone())?;
+        let tx = tb.build_offline()?;
+
+        assert_eq!(witness_idx, 1);
+        assert_eq!(tx.witnesses()[witness_idx as usize].as_vec(), &proof);
+        assert_eq!(tx.witness_count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_data_references_the_proof_witness() -> Result<()> {
+        let proof = vec![9u8; 64];
+        let (mut tb, witness_idx) =
+            given_a_base_asset_transfer(vec![]).add_proof_witness(proof.clone())?;
+
+        let coin = Coin {
+            amount: 100,
+            utxo_id: UtxoId::new(Bytes32::new([1; 32]), 0),
+            ..Default::default()
+        };
+        let predicate_data = (witness_idx as u64).to_be_bytes().to_vec();
+        tb.inputs_mut().push(
+            Input::resource_predicate(
+                CoinType::Coin(coin),
+                vec![1, 2, 3],
+                UnresolvedBytes::new(vec![Data::Inline(predicate_data.clone())]),
+            )
+            .with_predicate_gas_used(0),
+        );
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.witnesses()[witness_idx as usize].as_vec(), &proof);
+        assert_eq!(
+            tx.inputs()[1].input_predicate_data(),
+            Some(predicate_data.as_slice())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn witness_exceeding_the_witness_limit_is_rejected() {
+        let mut tb = given_a_base_asset_transfer(vec![])
+            .with_tx_policies(TxPolicies::default().with_witness_limit(128));
+
+        let result = tb.add_witness(vec![7u8; 100]);
+
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("witness limit")));
+        assert!(tb.witnesses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn overflowing_amounts_are_rejected() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new(FUEL_BECH32_HRP, PublicKey::from(&secret_key).hash());
+        let resources = vec![
+            CoinType::Coin(Coin {
+                amount: u64::MAX,
+                owner: owner.clone(),
+                ..Default::default()
+            });
+            2
+        ];
+
+        let consolidation_result = ScriptTransactionBuilder::prepare_consolidation(
+            resources.clone(),
+            Address::zeroed(),
+            vec![secret_key],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+        assert!(matches!(
+            consolidation_result,
+            Err(Error::InvalidData(msg)) if msg == "sum of amounts overflows `u64`"
+        ));
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            resources.into_iter().map(Input::resource_signed).collect(),
+            vec![Output::change(Address::zeroed(), 0, BASE_ASSET_ID)],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+        tb.add_unresolved_signature(owner, secret_key);
+        let change_result = tb.predicted_change_amount(MockDryRunner::default()).await;
+        assert!(matches!(
+            change_result,
+            Err(Error::InvalidData(msg)) if msg == "sum of amounts overflows `u64`"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn contract_interactions_are_detected() {
+        let owner = Bech32Address::default();
+        assert!(!given_a_signed_transfer(&owner).interacts_with_contracts());
+
+        let contract_id = ContractId::from([1u8; 32]);
+        let tb = ScriptTransactionBuilder::prepare_contract_call(
+            contract_id,
+            [3u8; 8],
+            vec![],
+            0,
+            BASE_ASSET_ID,
+            None,
+            vec![Input::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract_id,
+            )],
+            vec![Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed())],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+        assert!(tb.interacts_with_contracts());
+    }
+
+    #[test]
+    fn called_contract_ids_are_collected_from_contract_inputs() {
+        let given_a_contract_input = |contract_id| {
+            Input::contract(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                contract_id,
+            )
+        };
+        let contract_ids = [ContractId::new([1; 32]), ContractId::new([2; 32])];
+
+        let tb = ScriptTransactionBuilder::new(given_network_info()).with_inputs(vec![
+            given_a_contract_input(contract_ids[0]),
+            given_a_predicate_input(None),
+            given_a_contract_input(contract_ids[1]),
+        ]);
+
+        assert_eq!(tb.called_contract_ids(), contract_ids);
+    }
+
+    #[test]
+    fn duplicated_predicate_code_size_is_reported() -> Result<()> {
+        let given_predicate_inputs = |count: usize| {
+            let input = given_a_predicate(vec![1; 60], UnresolvedBytes::default())
+                .with_predicate_gas_used(0);
+
+            vec![input; count]
+        };
+        let tx_size = |tb: ScriptTransactionBuilder| -> Result<usize> {
+            Ok(tb.build_offline()?.metered_bytes_size())
+        };
+
+        let tb = ScriptTransactionBuilder::new(given_network_info());
+        let single_tb = tb.clone().with_inputs(given_predicate_inputs(1));
+        let duplicated_tb = tb.with_inputs(given_predicate_inputs(3));
+
+        assert_eq!(single_tb.duplicated_predicate_code_size(), 0);
+        assert_eq!(duplicated_tb.duplicated_predicate_code_size(), 2 * 64);
+
+        let size_per_input = tx_size(single_tb.clone())? - tx_size(single_tb.with_inputs(vec![]))?;
+        assert!(size_per_input > 64);
+        assert_eq!(
+            tx_size(duplicated_tb.clone())?,
+            tx_size(duplicated_tb.with_inputs(vec![]))? + 3 * size_per_input
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn witness_count_includes_generated_signatures() -> Result<()> {
+        let tb =
+            given_a_base_asset_transfer(vec![]).with_witnesses(vec![Witness::from(vec![1u8; 8])]);
+
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.witness_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_builds_are_byte_identical() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
+
+        let build = |tb: ScriptTransactionBuilder| -> Result<Vec<u8>> {
+            let tx = tb.build_deterministic(100)?;
+            Ok(FuelTransaction::from(tx).to_bytes())
+        };
+
+        assert_eq!(build(tb.clone())?, build(tb)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolved_policies_match_the_built_tx() -> Result<()> {
+        let mut raw_policies = Policies::default();
+        raw_policies.set(PolicyType::WitnessLimit, Some(2_000));
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_tx_policies(TxPolicies::default().with_max_fee(1_000))
+            .with_maturity(3)
+            .with_raw_policies(raw_policies);
+
+        let policies = tb.resolved_policies();
+        let tx = tb.build_offline()?;
+
+        assert_eq!(policies.to_bytes(), tx.tx.policies().to_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_budget_utilization_includes_the_min_gas() -> Result<()> {
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: ConsensusParameters {
+                gas_costs: GasCosts::free(),
+                fee_params: FeeParameters {
+                    gas_per_byte: 0,
+                    ..Default::default()
+                },
+                tx_params: TxParameters::default().with_max_gas_per_tx(10_000),
+                ..Default::default()
+            },
+        };
+        let tb = ScriptTransactionBuilder::new(network_info)
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
+
+        assert_eq!(tb.gas_budget_utilization(4_000)?, 0.4);
+
+        let tb = tb.with_gas_costs_override(GasCosts::default());
+        assert!(tb.gas_budget_utilization(4_000)? > 0.4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fee_components_split_script_and_predicate_gas() -> Result<()> {
+        let code: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+        let predicate_input = Input::resource_predicate(
+            CoinType::Coin(Coin {
+                amount: 100,
+                utxo_id: UtxoId::new([1; 32].into(), 0),
+                owner: FuelInput::predicate_owner(&code).into(),
+                ..Default::default()
+            }),
+            code,
+            UnresolvedBytes::default(),
+        );
+        let mut tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_price(1_000_000_000);
+        tb.inputs.push(predicate_input);
+        let dry_runner = MockDryRunner { gas_used: 100 };
+
+        let (script_gas_fee, predicate_gas_fee) = tb.fee_components(dry_runner).await?;
+
+        // The predicate does not provide its gas so it has to be estimated
+        let mut tx = tb.clone().build(dry_runner).await?;
+        tx.estimate_predicates(tb.consensus_parameters())?;
+        let predicate_gas: u64 = tx
+            .tx
+            .inputs()
+            .iter()
+            .filter_map(FuelInput::predicate_gas_used)
+            .sum();
+        assert!(predicate_gas > 0);
+
+        let fee_params = tb.consensus_parameters().fee_params();
+        assert_eq!(
+            Some(script_gas_fee),
+            gas_to_fee(100, 1_000_000_000, fee_params)
+        );
+        assert_eq!(
+            Some(predicate_gas_fee),
+            gas_to_fee(predicate_gas, 1_000_000_000, fee_params)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn post_build_hook_changes_are_in_the_built_tx() -> Result<()> {
+        let recipient = Address::new([9; 32]);
+        let tb = given_a_base_asset_transfer(vec![Output::coin(recipient, 10, BASE_ASSET_ID)])
+            .with_post_build_hook(|tx: &mut Script| {
+                if let Some(Output::Coin { amount, .. }) = tx.outputs_mut().first_mut() {
+                    *amount = 20;
+                }
+            });
+
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.outputs()[0], Output::coin(recipient, 20, BASE_ASSET_ID));
+
+        Ok(())
+    }
+
+    #[test]
+    fn transactions_spending_the_same_resource_conflict() {
+        let message = given_a_message(vec![]);
+        let tb = given_a_base_asset_transfer(vec![]);
+        let same_coin =
+            given_a_base_asset_transfer(vec![Output::change(Address::zeroed(), 0, BASE_ASSET_ID)]);
+        let with_message = ScriptTransactionBuilder::new(given_network_info()).with_inputs(vec![
+            Input::resource_signed(CoinType::Message(message.clone())),
+        ]);
+        let same_message = CreateTransactionBuilder::new(given_network_info()).with_inputs(vec![
+            Input::resource_predicate(
+                CoinType::Message(message),
+                vec![1, 2, 3],
+                UnresolvedBytes::default(),
+            ),
+        ]);
+
+        assert!(transactions_conflict(&tb, &same_coin));
+        assert!(transactions_conflict(&with_message, &same_message));
+        assert!(!transactions_conflict(&tb, &with_message));
+        assert!(!transactions_conflict(&tb, &same_message));
+    }
+
+    #[test]
+    fn predicate_args_are_encoded_into_the_predicate_data() -> Result<()> {
+        let code = vec![op::ret(RegId::ONE)].into_iter().collect::<Vec<u8>>();
+        let args = [Token::U64(42), Token::Bool(true)];
+
+        let tb = ScriptTransactionBuilder::new(given_network_info()).add_predicate_with_args(
+            CoinType::Coin(Coin::default()),
+            code.clone(),
+            &args,
+        )?;
+
+        let [Input::ResourcePredicate {
+            code: input_code,
+            data,
+            ..
+        }] = &tb.inputs[..]
+        else {
+            panic!("expected a single predicate input");
+        };
+        assert_eq!(*input_code, code);
+        assert_eq!(
+            data.resolve(0),
+            [vec![0; 7], vec![42], vec![1], vec![0; 7]].concat()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_artifact_configurables_and_args_are_applied() -> Result<()> {
+        let artifact = given_a_predicate_artifact()?;
+        let args = vec![
+            ConfigurableValue::new("switch", Token::Bool(false)),
+            ConfigurableValue::new("U8", Token::U8(42)),
+            ConfigurableValue::new("u_8", Token::U8(42)),
+        ];
+
+        let tb = ScriptTransactionBuilder::new(given_network_info()).add_predicate_from_artifact(
+            CoinType::Coin(Coin::default()),
+            &artifact,
+            args,
+        )?;
+
+        let [Input::ResourcePredicate { code, data, .. }] = &tb.inputs[..] else {
+            panic!("expected a single predicate input");
+        };
+        // `U8` is set while `BOOL` keeps its default
+        let expected_code = [&artifact.code()[..8], &[0; 7], &[42], &[0; 7], &[1]].concat();
+        assert_eq!(*code, expected_code);
+        assert_eq!(
+            data.resolve(0),
+            ABIEncoder::encode(&[Token::U8(42), Token::Bool(false)])?.resolve(0)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_artifact_values_are_checked_against_the_abi() -> Result<()> {
+        let artifact = given_a_predicate_artifact()?;
+        let arguments = || {
+            vec![
+                ConfigurableValue::new("u_8", Token::U8(8)),
+                ConfigurableValue::new("switch", Token::Bool(true)),
+            ]
+        };
+
+        for (args, expected_msg) in [
+            (
+                [
+                    arguments(),
+                    vec![ConfigurableValue::new("OTHER", Token::U8(1))],
+                ]
+                .concat(),
+                "`OTHER` is neither a configurable nor an argument of the predicate",
+            ),
+            (
+                arguments()[..1].to_vec(),
+                "missing a value for the predicate argument `switch`",
+            ),
+            (
+                [arguments(), arguments()[..1].to_vec()].concat(),
+                "value for `u_8` was given twice",
+            ),
+            (
+                [
+                    arguments(),
+                    vec![ConfigurableValue::new("BOOL", Token::B256([1; 32]))],
+                ]
+                .concat(),
+                "value of configurable `BOOL` does not match its type `Bool`",
+            ),
+        ] {
+            let err = artifact
+                .code_and_data(args)
+                .expect_err("should fail because of the values");
+
+            let Error::InvalidData(msg) = err else {
+                panic!("expected invalid data error")
+            };
+            assert_eq!(msg, expected_msg);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fitted_witness_limit_matches_the_witnesses_size() -> Result<()> {
+        let mut tb =
+            given_a_base_asset_transfer(vec![]).with_witnesses(vec![Witness::from(vec![1u8; 10])]);
+
+        tb.fit_witness_limit()?;
+        assert!(tb.clone().add_witness(vec![1]).is_err());
+
+        let consensus_parameters = tb.consensus_parameters().clone();
+        let tx = tb.clone().build_offline()?;
+        let witnesses_size: usize = tx.witnesses().iter().map(|witness| witness.size()).sum();
+        assert_eq!(
+            tx.tx.policies().get(PolicyType::WitnessLimit),
+            Some(witnesses_size as u64)
+        );
+
+        // The exact size passes the checks of the node, one byte less does not
+        let check = |tx: ScriptTransaction| {
+            tx.tx
+                .check_without_signatures(0u32.into(), &consensus_parameters)
+        };
+        assert_eq!(check(tx), Ok(()));
+        tb.witness_limit = Some(witnesses_size as u64 - 1);
+        assert_eq!(
+            check(tb.build_offline()?),
+            Err(ValidityError::TransactionWitnessLimitExceeded)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn transactions_instantiated_from_a_template_share_its_shape() -> Result<()> {
+        let recipient = Address::new([9; 32]);
+        let template =
+            given_a_base_asset_transfer(vec![Output::coin(recipient, 10, BASE_ASSET_ID)])
+                .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+                .with_gas_limit(100)
+                .template();
+
+        let txs = [1, 2]
+            .map(|seed| {
+                let secret_key = given_a_secret_key(seed);
+                let coin = Coin {
+                    amount: 1_000,
+                    owner: Bech32Address::new("fuel", PublicKey::from(&secret_key).hash()),
+                    ..Default::default()
+                };
+
+                ScriptTransactionBuilder::instantiate(
+                    &template,
+                    vec![Input::resource_signed(CoinType::Coin(coin))],
+                    vec![secret_key],
+                    given_network_info(),
+                )
+                .build_offline()
+            })
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+
+        for tx in &txs {
+            assert_eq!(tx.outputs(), template.outputs.as_slice());
+            assert_eq!(*tx.tx.script(), template.script);
+            assert_eq!(*tx.tx.script_gas_limit(), 100);
+            assert_eq!(tx.tx.policies().get(PolicyType::GasPrice), Some(1));
+            assert_eq!(tx.witnesses().len(), 1);
+        }
+        assert_ne!(txs[0].inputs(), txs[1].inputs());
+
+        Ok(())
+    }
+
+    #[test]
+    fn deferred_script_has_to_be_filled_before_building() -> Result<()> {
+        let mut tb = given_a_base_asset_transfer(vec![])
+            .with_gas_limit(100)
+            .with_deferred_script();
+
+        let result = tb.clone().build_offline();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.contains("never filled")
+        ));
+
+        let script: Vec<u8> = vec![op::ret(RegId::ONE)].into_iter().collect();
+        tb.fill_script(script.clone(), vec![1, 2, 3])?;
+        let tx = tb.clone().build_offline()?;
+
+        assert_eq!(tx.script(), &script);
+        assert_eq!(tx.script_data(), &vec![1, 2, 3]);
+        assert!(tb.fill_script(vec![], vec![]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_limit_over_the_max_gas_per_tx_follows_the_behavior() -> Result<()> {
+        let mut network_info = given_network_info();
+        network_info.consensus_parameters.tx_params =
+            TxParameters::default().with_max_gas_per_tx(10_000);
+        let tb = ScriptTransactionBuilder::new(network_info)
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_limit(20_000);
+
+        let result = tb.clone().build_offline();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.contains("higher than the max gas per tx")
+        ));
+
+        let tx = tb
+            .clone()
+            .with_over_limit_behavior(OverLimitBehavior::Clamp)
+            .build_offline()?;
+        assert_eq!(*tx.tx.script_gas_limit(), 10_000);
+
+        let tx = tb
+            .with_over_limit_behavior(OverLimitBehavior::Allow)
+            .build_offline()?;
+        assert_eq!(*tx.tx.script_gas_limit(), 20_000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_price_below_the_network_minimum_is_rejected() {
+        let network_info = NetworkInfo {
+            min_gas_price: 7,
+            ..given_network_info()
+        };
+        let tb = ScriptTransactionBuilder::new(network_info).with_gas_price(0);
+
+        assert!(matches!(
+            tb.build_errors()[..],
+            [BuildError::GasPriceBelowMinimum {
+                gas_price: 0,
+                min_gas_price: 7,
+            }]
+        ));
+        assert!(matches!(
+            tb.build_offline(),
+            Err(Error::InvalidData(msg))
+                if msg == "gas price `0` is lower than the network minimum of `7`"
+        ));
+    }
+
+    #[test]
+    fn registered_signers_must_match_the_required_signers() -> Result<()> {
+        let signer = Bech32Address::new("fuel", PublicKey::from(&given_a_secret_key(1)).hash());
+        let other = Bech32Address::new("fuel", PublicKey::from(&given_a_secret_key(2)).hash());
+
+        let tb = given_a_base_asset_transfer(vec![]);
+
+        // missing
+        let missing = tb
+            .clone()
+            .with_required_signers(vec![signer.clone(), other.clone()]);
+        assert!(matches!(
+            &missing.build_errors()[..],
+            [BuildError::MissingRequiredSigner { owner }] if *owner == other
+        ));
+        assert!(missing.build_offline().is_err());
+
+        // extra
+        let mut extra = tb.clone().with_required_signers(vec![signer.clone()]);
+        extra.add_unresolved_signature(other.clone(), given_a_secret_key(2));
+        assert!(matches!(
+            &extra.build_errors()[..],
+            [BuildError::UnexpectedSigner { owner }] if *owner == other
+        ));
+        assert!(extra.build_offline().is_err());
+
+        // exact
+        tb.with_required_signers(vec![signer]).build_offline()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn remaining_input_capacity_respects_the_tx_limits() -> Result<()> {
+        let network_info = NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: ConsensusParameters {
+                tx_params: TxParameters::default().with_max_inputs(3),
+                ..Default::default()
+            },
+        };
+        let mut tb = given_a_base_asset_transfer(vec![]);
+        tb.network_info = network_info;
+
+        assert_eq!(tb.remaining_input_capacity()?, 2);
+
+        let coin = Coin {
+            amount: 1,
+            owner: Bech32Address::new("fuel", PublicKey::from(&given_a_secret_key(1)).hash()),
+            ..Default::default()
+        };
+        tb.inputs
+            .push(Input::resource_signed(CoinType::Coin(coin.clone())));
+        tb.inputs.push(Input::resource_signed(CoinType::Coin(coin)));
+        assert_eq!(tb.remaining_input_capacity()?, 0);
+
+        tb.inputs.pop();
+        let tx_size = tb.clone().build_offline()?.tx.size();
+        let signature_size = Witness::from(vec![0u8; Signature::LEN]).size();
+        let input_size = create_coin_input(Coin::default(), 0).size() + signature_size;
+
+        // The signature of the transfer already takes one `signature_size`
+        tb.witness_limit = Some(2 * signature_size as u64);
+        assert_eq!(tb.remaining_input_capacity()?, 1);
+        tb.witness_limit = Some(2 * signature_size as u64 - 1);
+        assert_eq!(tb.remaining_input_capacity()?, 0);
+        tb.witness_limit = None;
+
+        assert_eq!(
+            tb.clone()
+                .with_max_size(tx_size + input_size)
+                .remaining_input_capacity()?,
+            1
+        );
+        assert_eq!(
+            tb.with_max_size(tx_size + input_size - 1)
+                .remaining_input_capacity()?,
+            0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn minimum_acceptable_gas_price_is_the_network_minimum() -> Result<()> {
+        let network_info = NetworkInfo {
+            min_gas_price: 7,
+            ..given_network_info()
+        };
+        let tb = ScriptTransactionBuilder::new(network_info);
+
+        assert_eq!(tb.minimum_acceptable_gas_price(), 7);
+        assert_eq!(tb.build_offline()?.gas_price(), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn effective_maturity_matches_the_built_tx() -> Result<()> {
+        let tb = ScriptTransactionBuilder::new(given_network_info());
+        assert_eq!(tb.effective_maturity(), 0);
+        assert_eq!(tb.build_offline()?.maturity(), 0);
+
+        let tb = ScriptTransactionBuilder::new(given_network_info()).with_maturity(5);
+        assert_eq!(tb.effective_maturity(), 5);
+        assert_eq!(tb.build_offline()?.maturity(), 5);
+
+        let mut raw_policies = Policies::default();
+        raw_policies.set(PolicyType::Maturity, Some(9));
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_maturity(5)
+            .with_raw_policies(raw_policies);
+        assert_eq!(tb.effective_maturity(), 9);
+        assert_eq!(tb.build_offline()?.maturity(), 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dry_run_is_only_required_for_scripts_without_gas_limit() {
+        let script = vec![op::ret(RegId::ONE)].into_iter().collect::<Vec<u8>>();
+        let tb = ScriptTransactionBuilder::new(given_network_info());
+
+        assert!(!tb.requires_dry_run());
+        assert!(tb.clone().with_script(script.clone()).requires_dry_run());
+        assert!(!tb
+            .with_script(script)
+            .with_tx_policies(TxPolicies::default().with_script_gas_limit(100))
+            .requires_dry_run());
+
+        let create_tb = CreateTransactionBuilder::prepare_contract_deployment(
+            vec![1u8; 8],
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+        assert!(!create_tb.requires_dry_run());
+    }
+
+    #[test]
+    fn signer_map_lists_signers_by_witness_index() {
+        let owners: Vec<_> = (1..=3)
+            .map(|seed| Bech32Address::new("fuel", [seed; 32]))
+            .collect();
+        let mut tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![Witness::default()]);
+        for (seed, owner) in owners.iter().enumerate().rev() {
+            tb.add_unresolved_signature(owner.clone(), given_a_secret_key(seed as u8 + 1));
+        }
+
+        let expected = vec![
+            (1, (&owners[2]).into()),
+            (2, (&owners[1]).into()),
+            (3, (&owners[0]).into()),
+        ];
+        assert_eq!(tb.signer_map(), expected);
+    }
+
+    #[test]
+    fn change_outputs_for_the_same_asset_must_have_the_same_recipient() {
+        let asset_id = AssetId::from([1u8; 32]);
+        let outputs = vec![
+            Output::change(Address::from([2u8; 32]), 0, asset_id),
+            Output::change(Address::from([3u8; 32]), 0, asset_id),
+        ];
+
+        let result = CreateTransactionBuilder::new(given_network_info())
+            .with_outputs(outputs)
+            .build();
+
+        let Err(Error::InvalidData(msg)) = result else {
+            panic!("expected conflicting change outputs to be rejected");
+        };
+        assert!(msg.contains("indexes `0` and `1`"));
+    }
+
+    #[derive(Debug)]
+    struct MockPasskeySigner {
+        address: Bech32Address,
+        secret_key: SecretKey,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ExternalSigner for MockPasskeySigner {
+        fn address(&self) -> &Bech32Address {
+            &self.address
+        }
+
+        async fn sign_raw(&self, message: [u8; 32]) -> Result<RawSignature> {
+            let signature = Signature::sign(&self.secret_key, &CryptoMessage::from_bytes(message));
+
+            // Split the compact signature back into the raw form an authenticator returns
+            let mut bytes = *signature;
+            let recovery_id = bytes[32] >> 7;
+            bytes[32] &= 0x7f;
+
+            Ok(RawSignature { bytes, recovery_id })
+        }
+    }
+
+    #[test]
+    fn tx_larger_than_the_max_size_is_rejected() -> Result<()> {
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![Witness::from(vec![1u8; 1024])]);
+        let tx_size = tb.clone().build_offline()?.tx.size();
+
+        assert!(tb.clone().with_max_size(tx_size).build_offline().is_ok());
+
+        let result = tb.with_max_size(tx_size - 1).build_offline();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.contains("more than the max size")
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn script_is_padded_with_noops() -> Result<()> {
+        let script: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(script.clone())
+            .with_script_padding(5 * Instruction::SIZE)
+            .with_gas_limit(100);
+
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.script().len(), 5 * Instruction::SIZE);
+        assert_eq!(&tx.script()[..Instruction::SIZE], script.as_slice());
+        let padding = fuel_asm::from_bytes(tx.script()[Instruction::SIZE..].iter().copied())
+            .collect::<std::result::Result<Vec<Instruction>, _>>()
+            .expect("should be valid instructions");
+        assert!(padding
+            .iter()
+            .all(|instruction| instruction.opcode() == Opcode::NOOP));
+
+        let result = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(script)
+            .with_script_padding(0)
+            .with_gas_limit(100)
+            .build_offline();
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg == "script is `4` bytes long but has to be padded to `0` bytes"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn script_padding_is_checked_when_set() -> Result<()> {
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
+
+        let padded = tb.clone().try_with_script_padding(2 * Instruction::SIZE)?;
+        assert_eq!(padded.script.len(), 2 * Instruction::SIZE);
+
+        let result = tb.clone().try_with_script_padding(Instruction::SIZE + 1);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg == "script padding `5` is not a multiple of the instruction size `4`"
+        ));
+
+        let result = tb.try_with_script_padding(0);
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg == "script is `4` bytes long and can not be padded to `0` bytes"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rebuilt_tx_keeps_its_id_once_signed_again() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let address = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let mut tb = given_a_signed_transfer(&address)
+            .with_witnesses(vec![Witness::from(vec![1u8; 8])])
+            .with_maturity(7);
+        tb.add_unresolved_signature(address.clone(), secret_key);
+        let tx = tb.build_offline()?;
+        let bytes = FuelTransaction::from(tx.clone()).to_bytes();
+
+        let mut rebuilt_tb =
+            ScriptTransactionBuilder::rebuild_for_signing(&bytes, given_network_info())?;
+        rebuilt_tb.add_unresolved_signature(address, secret_key);
+        let rebuilt_tx = rebuilt_tb.build_offline()?;
+
+        let chain_id = given_network_info().chain_id();
+        assert_eq!(rebuilt_tx.id(chain_id), tx.id(chain_id));
+        assert_eq!(rebuilt_tx.witnesses(), tx.witnesses());
+
+        Ok(())
+    }
+
+    #[test]
+    fn witness_index_outside_of_the_witnesses_is_rejected() {
+        let secret_key = given_a_secret_key(1);
+        let address = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let mut tb = given_a_signed_transfer(&address);
+        tb.add_unresolved_signature(address.clone(), secret_key);
+        tb.unresolved_signatures
+            .addr_idx_offset_map
+            .insert(address, 5);
+
+        let result = tb.build_offline();
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg))
+                if msg == "input at index `0` references witness `5` but the tx only has `1` witnesses"
+        ));
+    }
+
+    #[test]
+    fn duplicated_witnesses_are_removed() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let address = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let mut tb = given_a_signed_transfer(&address).with_witnesses(vec![
+            Witness::from(vec![1u8; 64]),
+            Witness::from(vec![2u8; 64]),
+            Witness::from(vec![1u8; 64]),
+        ]);
+        tb.add_unresolved_signature(address.clone(), secret_key);
+
+        assert_eq!(tb.dedup_witnesses(), vec![0, 1, 0]);
+        let tx = tb.build_offline()?;
+
+        assert_eq!(tx.witnesses().len(), 3);
+        assert_eq!(tx.inputs()[0].witness_index(), Some(2));
+
+        let mut tb = CreateTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![
+                Witness::from(vec![1u8; 64]),
+                Witness::from(vec![2u8; 64]),
+                Witness::from(vec![2u8; 64]),
+            ])
+            .with_bytecode_witness_index(2);
+        tb.dedup_witnesses();
+
+        assert_eq!(tb.witnesses().len(), 2);
+        assert_eq!(tb.bytecode_witness_index, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytecode_witness_position_is_not_shifted_by_signers() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let mut tb = CreateTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![given_a_coin_input(100, owner.clone())])
+            .with_witnesses(vec![Witness::from(vec![1u8; 8])]);
+        tb.add_unresolved_signature(owner, secret_key);
+
+        let position = tb.bytecode_witness_position();
+        let binary = Witness::from(vec![2u8; 16]);
+        tb.witnesses_mut().push(binary.clone());
+        let tx = tb.with_bytecode_witness_index(position).build_offline()?;
+
+        assert_eq!(position, 1);
+        assert_eq!(tx.witnesses().len(), 3);
+        assert_eq!(tx.witnesses()[position as usize], binary);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn external_signer_witness_is_accepted() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let address = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let mut tb = given_a_signed_transfer(&address);
+        tb.add_external_signer(MockPasskeySigner {
+            address: address.clone(),
+            secret_key,
+        });
+
+        let tx = tb.build(MockDryRunner::default()).await?;
+
+        let expected_signature =
+            Signature::sign(&secret_key, &CryptoMessage::from_bytes(*tx.id(0.into())));
+        assert_eq!(tx.inputs()[0].witness_index(), Some(0));
+        assert_eq!(tx.witnesses()[0].as_ref(), expected_signature.as_ref());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn external_signature_must_recover_to_the_signer_address() {
+        let address = Bech32Address::new("fuel", [1u8; 32]);
+        let mut tb = given_a_signed_transfer(&address);
+        tb.add_external_signer(MockPasskeySigner {
+            address,
+            secret_key: given_a_secret_key(1),
+        });
+
+        let result = tb.build(MockDryRunner::default()).await;
+
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("does not belong")));
+    }
+
+    #[tokio::test]
+    async fn marginal_input_fee_matches_the_fee_difference() -> Result<()> {
+        let network_info = NetworkInfo {
+            consensus_parameters: ConsensusParameters {
+                gas_costs: GasCosts::free(),
+                fee_params: FeeParameters {
+                    gas_price_factor: 1,
+                    gas_per_byte: 4,
+                },
+                ..Default::default()
+            },
+            min_gas_price: 1,
+        };
+        let consensus_parameters = network_info.consensus_parameters.clone();
+        let owner = Bech32Address::new("fuel", [1u8; 32]);
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![given_a_coin_input(100, owner.clone())],
+            vec![],
+            TxPolicies::default(),
+            network_info,
+        );
+        tb.add_unresolved_signature(owner.clone(), given_a_secret_key(1));
+        let extra_input = given_a_coin_input(200, owner.clone());
+
+        let marginal_fee = tb.marginal_input_fee(&extra_input)?;
+
+        let min_fee = |tx: ScriptTransaction| {
+            tx.fee_checked_from_tx(&consensus_parameters)
+                .expect("fee should not overflow")
+                .min_fee()
+        };
+        let fee_without_input = min_fee(tb.clone().build(MockDryRunner::default()).await?);
+        let fee_with_input = min_fee(
+            tb.with_inputs(vec![given_a_coin_input(100, owner), extra_input])
+                .build(MockDryRunner::default())
+                .await?,
+        );
+        assert_eq!(fee_with_input - fee_without_input, marginal_fee);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cached_dry_runner_reuses_results_for_unchanged_txs() -> Result<()> {
+        let dry_runner = CachedDryRunner::new(CountingDryRunner::default());
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
+
+        tb.fee_checked_from_tx(&dry_runner).await?;
+        tb.clone().build(&dry_runner).await?;
+        assert_eq!(dry_runner.dry_runner.count.load(Ordering::SeqCst), 1);
+
+        tb.with_script_data(vec![1, 2, 3])
+            .build(&dry_runner)
+            .await?;
+        assert_eq!(dry_runner.dry_runner.count.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pure_transfer_is_built_without_dry_running() -> Result<()> {
+        let dry_runner = CountingDryRunner::default();
+        let tb = given_a_base_asset_transfer(vec![]).as_pure_transfer();
+
+        tb.clone().build(&dry_runner).await?;
+        assert_eq!(dry_runner.count.load(Ordering::SeqCst), 0);
+
+        let err = tb
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .build(&dry_runner)
+            .await
+            .expect_err("should fail because of the script");
+
+        let Error::InvalidData(msg) = err else {
+            panic!("expected invalid data error")
+        };
+        assert_eq!(
+            msg,
+            "a script was added to a transaction marked as a pure transfer"
+        );
+        assert_eq!(dry_runner.count.load(Ordering::SeqCst), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rounded_fee_never_underestimates_the_gas() {
+        for gas_price_factor in [1, 2, 92, 1_000, 1_000_000_000] {
+            let fee_params = FeeParameters {
+                gas_price_factor,
+                ..Default::default()
+            };
+            for gas_price in [0, 1, 3, 91, 92, 1_000, u32::MAX as u64] {
+                let margin = gas_rounding_margin(gas_price, &fee_params);
+                let mut highest_extra_gas = 0;
+
+                for gas in (0..200).chain([1_000_003, 100_000_000]) {
+                    let fee = gas_to_fee(gas, gas_price, &fee_params).expect("should not overflow");
+
+                    let charged = fee as u128 * gas_price_factor as u128;
+                    assert!(charged >= gas as u128 * gas_price as u128);
+
+                    let covered_gas = charged
+                        .checked_div(gas_price as u128)
+                        .unwrap_or(gas as u128) as u64;
+                    assert!(covered_gas - gas <= margin);
+                    highest_extra_gas = highest_extra_gas.max(covered_gas - gas);
+                }
+
+                if gas_price_factor <= 200 {
+                    assert_eq!(highest_extra_gas, margin);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_script_gas_limit_accounts_for_the_fee_rounding() {
+        for (gas_price_factor, gas_price) in [(1, 1), (92, 0), (92, 1), (92, 10), (1_000, 7)] {
+            let mut network_info = given_network_info();
+            network_info.consensus_parameters.fee_params = FeeParameters {
+                gas_price_factor,
+                ..Default::default()
+            };
+            let mut tx = FuelTransaction::script(
+                0,
+                vec![],
+                vec![],
+                Policies::default().with_gas_price(gas_price),
+                vec![],
+                vec![],
+                vec![],
+            );
+
+            ScriptTransactionBuilder::set_max_script_gas_limit(&mut tx, &network_info);
+
+            // The `script_gas_limit` is part of `max_gas`, the margin is left for the rounding
+            let consensus_params = &network_info.consensus_parameters;
+            let fee_params = consensus_params.fee_params();
+            let max_gas = tx.max_gas(consensus_params.gas_costs(), fee_params);
+            assert_eq!(
+                max_gas + gas_rounding_margin(gas_price, fee_params),
+                network_info.max_gas_per_tx() / 2
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn gas_headroom_is_added_to_the_estimation() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
+        let dry_runner = MockDryRunner { gas_used: 100 };
+
+        let tx = tb.clone().with_gas_headroom(50).build(&dry_runner).await?;
+        assert_eq!(*tx.tx.script_gas_limit(), 150);
+
+        let max_gas_per_tx = tb.network_info.max_gas_per_tx();
+        let tx = tb.with_gas_headroom(u64::MAX).build(&dry_runner).await?;
+        assert_eq!(*tx.tx.script_gas_limit(), max_gas_per_tx);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_gas_estimation_tolerance_is_rejected() -> Result<()> {
+        for tolerance in [f32::NAN, -0.1] {
+            let result = ScriptTransactionBuilder::new(given_network_info())
+                .try_with_gas_estimation_tolerance(tolerance);
+            assert!(matches!(result, Err(Error::InvalidData(_))));
+
+            let result = ScriptTransactionBuilder::new(given_network_info())
+                .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+                .with_gas_estimation_tolerance(tolerance)
+                .build(MockDryRunner::default())
+                .await;
+            assert!(matches!(result, Err(Error::InvalidData(_))));
+        }
+
+        ScriptTransactionBuilder::new(given_network_info())
+            .try_with_gas_estimation_tolerance(0.2)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn variable_outputs_are_appended() {
+        let coin_output = Output::coin(Address::default(), 1, BASE_ASSET_ID);
+
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_outputs(vec![coin_output])
+            .with_variable_outputs(3);
+
+        assert_eq!(tb.outputs.len(), 4);
+        assert_eq!(tb.outputs[0], coin_output);
+        assert!(tb.outputs[1..]
+            .iter()
+            .all(|output| *output == Output::variable(Address::zeroed(), 0, AssetId::zeroed())));
+    }
+
+    #[tokio::test]
+    async fn bytecode_witness_index_must_point_to_the_binary() {
+        let result = CreateTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![Witness::from(vec![1u8; 8])])
+            .with_bytecode_witness_index(1)
+            .build();
+        assert!(
+            matches!(result, Err(Error::InvalidData(msg)) if msg.contains("only has `1` witnesses"))
+        );
+
+        let tb = CreateTransactionBuilder::new(given_network_info())
+            .with_witnesses(vec![Witness::default()]);
+        assert!(tb.clone().build().is_ok());
+        assert!(matches!(
+            tb.try_build(MockDryRunner::default()).await.unwrap_err()[..],
+            [BuildError::EmptyBytecodeWitness { index: 0 }]
+        ));
+    }
+
+    #[test]
+    fn outputs_can_not_exceed_the_consensus_limit() {
+        let max_outputs = ConsensusParameters::default().tx_params().max_outputs as usize;
+
+        let result = CreateTransactionBuilder::new(given_network_info())
+            .with_variable_outputs(max_outputs + 1)
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("outputs")));
+    }
+
+    #[tokio::test]
+    async fn try_build_returns_all_problems() {
+        let owner = Bech32Address::new("fuel", [1; 32]);
+        let recipient = Address::new([2; 32]);
+
+        let tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![given_a_coin_input(100, owner.clone())],
+            vec![
+                Output::coin(recipient, 1_000, BASE_ASSET_ID),
+                Output::change((&owner).into(), 0, BASE_ASSET_ID),
+                Output::change(recipient, 0, BASE_ASSET_ID),
+            ],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+
+        let Err(errors) = tb.try_build(MockDryRunner::default()).await else {
+            panic!("expected `try_build` to fail");
+        };
+
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            &errors[0],
+            BuildError::MissingSignature { input_idx: 0, owner: missing_owner } if *missing_owner == owner
+        ));
+        assert!(matches!(
+            errors[1],
+            BuildError::InsufficientBalance {
+                available: 100,
+                required: 1_000,
+                ..
+            }
+        ));
+        assert!(matches!(
+            errors[2],
+            BuildError::ConflictingChangeOutputs {
+                first_idx: 1,
+                second_idx: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn underfunded_message_to_output_is_rejected() {
+        let prepare = |amount| {
+            ScriptTransactionBuilder::try_prepare_message_to_output(
+                Address::zeroed(),
+                100,
+                vec![given_a_coin_input(amount, Bech32Address::default())],
+                TxPolicies::default().with_gas_price(1),
+                given_network_info(),
+            )
+        };
+
+        let result = prepare(100);
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("estimated fee")));
+
+        assert!(prepare(1_000).is_ok());
+    }
+
+    #[test]
+    fn message_to_output_change_goes_to_the_funder() {
+        let recipient = Address::new([1; 32]);
+        let funder = Address::new([2; 32]);
+
+        let tb = ScriptTransactionBuilder::prepare_message_to_output_with_change(
+            recipient,
+            funder,
+            100,
+            vec![],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+
+        assert_eq!(tb.outputs, vec![Output::change(funder, 0, BASE_ASSET_ID)]);
+        assert_eq!(&tb.script_data[..Address::LEN], recipient.as_ref());
+    }
+
+    #[test]
+    fn withdrawal_sends_a_message_with_data() {
+        let recipient = Address::new([1; 32]);
+        let funder = Address::new([2; 32]);
+        let data = vec![3u8; 20];
+
+        let tb = ScriptTransactionBuilder::prepare_withdrawal(
+            recipient,
+            100,
+            data.clone(),
+            funder,
+            vec![],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+
+        assert_eq!(tb.outputs, vec![Output::change(funder, 0, BASE_ASSET_ID)]);
+
+        let expected_script_data = [
+            recipient.to_vec(),
+            100u64.to_be_bytes().to_vec(),
+            20u64.to_be_bytes().to_vec(),
+            data,
+        ]
+        .concat();
+        assert_eq!(tb.script_data, expected_script_data);
+
+        let opcodes = fuel_asm::from_bytes(tb.script.iter().copied())
+            .map(|instruction| instruction.map(|instruction| instruction.opcode()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .expect("should be valid instructions");
+        assert_eq!(
+            opcodes,
+            vec![
+                Opcode::GTF,
+                Opcode::ADDI,
+                Opcode::LW,
+                Opcode::LW,
+                Opcode::ADDI,
+                Opcode::SMO,
+                Opcode::RET
+            ]
+        );
+    }
+
+    #[test]
+    fn built_tx_exposes_its_policies() -> Result<()> {
+        let tx_policies = TxPolicies::default()
+            .with_gas_price(3)
+            .with_witness_limit(5_000)
+            .with_maturity(10)
+            .with_max_fee(1_000);
+
+        let tx = CreateTransactionBuilder::new(given_network_info())
+            .with_tx_policies(tx_policies)
+            .with_witnesses(vec![Witness::from(vec![1u8; 8])])
+            .build()?;
+
+        assert_eq!(tx.gas_price(), 3);
+        assert_eq!(tx.witness_limit(), Some(5_000));
+        assert_eq!(tx.maturity(), 10);
+        assert_eq!(tx.max_fee(), Some(1_000));
+
+        Ok(())
+    }
+
+    #[test]
+    fn raw_policies_override_builder_policies() -> Result<()> {
+        let mut raw_policies = Policies::default();
+        raw_policies.set(PolicyType::GasPrice, Some(7));
+
+        let tx = CreateTransactionBuilder::new(given_network_info())
+            .with_gas_price(3)
+            .with_raw_policies(raw_policies)
+            .with_maturity(10)
+            .with_witnesses(vec![Witness::from(vec![1u8; 8])])
+            .build()?;
+
+        let policies = tx.tx.policies();
+        assert_eq!(policies.get(PolicyType::GasPrice), Some(7));
+        assert_eq!(policies.get(PolicyType::Maturity), Some(10));
+        assert_eq!(
+            policies.get(PolicyType::WitnessLimit),
+            Some(DEFAULT_CREATE_WITNESS_LIMIT)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_reports_the_changes() {
+        let owner = Bech32Address::new("fuel", [1u8; 32]);
+        let tb = given_a_signed_transfer(&owner).with_witnesses(vec![Witness::from(vec![1u8])]);
+        let removed_input = tb.inputs[0].clone();
+        let added_output = Output::coin(Address::zeroed(), 10, BASE_ASSET_ID);
+
+        let mut modified_tb = tb
+            .clone()
+            .with_witnesses(vec![Witness::from(vec![1u8]), Witness::from(vec![2u8])])
+            .with_maturity(4);
+        modified_tb.add_unresolved_signature(owner, given_a_secret_key(1));
+        modified_tb.inputs.clear();
+        modified_tb.outputs.push(added_output);
+
+        let diff = tb.diff(&modified_tb);
+
+        assert_eq!(
+            diff,
+            BuilderDiff {
+                added_inputs: vec![],
+                removed_inputs: vec![removed_input],
+                added_outputs: vec![added_output],
+                removed_outputs: vec![],
+                added_witnesses: vec![Witness::from(vec![2u8])],
+                removed_witnesses: vec![],
+                changed_policies: vec![(PolicyType::Maturity, Some(0), Some(4))],
+            }
+        );
+        assert!(tb.diff(&tb.clone()).is_empty());
+    }
+
+    #[test]
+    fn active_policies_match_the_built_tx() -> Result<()> {
+        let all_policy_types = [
+            PolicyType::GasPrice,
+            PolicyType::WitnessLimit,
+            PolicyType::Maturity,
+            PolicyType::MaxFee,
+        ];
+        let set_policy_types = |policies: &Policies| {
+            all_policy_types
+                .into_iter()
+                .filter(|policy_type| policies.get(*policy_type).is_some())
+                .collect::<Vec<_>>()
+        };
+
+        for max_fee in [None, Some(1_000)] {
+            let tx_policies = TxPolicies::new(Some(1), None, 10, max_fee, None);
+            let tb = CreateTransactionBuilder::new(given_network_info())
+                .with_tx_policies(tx_policies)
+                .with_witnesses(vec![Witness::from(vec![1u8; 8])]);
+
+            let active_policies = tb.active_policies();
+            let tx = tb.build()?;
+
+            assert_eq!(active_policies, set_policy_types(tx.tx.policies()));
+            assert_eq!(
+                active_policies.contains(&PolicyType::MaxFee),
+                max_fee.is_some()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn inputs_and_outputs_round_trip_through_json() -> Result<()> {
+        let predicate_input = Input::resource_predicate(
+            CoinType::Message(Message::new(
+                Bech32Address::default(),
+                Bech32Address::new(FUEL_BECH32_HRP, [2; 32]),
+                Nonce::new([3; 32]),
+                100,
+                vec![4, 5],
+            )),
+            vec![1, 2, 3],
+            UnresolvedBytes::new(vec![
+                Data::Inline(vec![6; 8]),
+                Data::Dynamic(vec![Data::Inline(vec![7; 8])]),
+            ]),
+        )
+        .with_predicate_gas_used(10);
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![
+                given_a_coin_input(100, Bech32Address::new(FUEL_BECH32_HRP, [1; 32])),
+                predicate_input,
+            ])
+            .with_outputs(vec![
+                Output::coin(Address::new([8; 32]), 50, BASE_ASSET_ID),
+                Output::change(Address::new([9; 32]), 0, BASE_ASSET_ID),
+            ])
+            .with_witnesses(vec![Witness::from(vec![1u8; 64])]);
 
-                if num_witnesses + self.unresolved_signatures.secret_keys.len() > 256 {
-                    return Err(error!(
-                        InvalidData,
-                        "tx can not have more than 256 witnesses"
-                    ));
-                }
+        let json = tb.to_json()?;
+        let decoded = ScriptTransactionBuilder::from_json(&json, given_network_info())?;
 
-                Ok(num_witnesses as u8)
-            }
+        assert_eq!(decoded.inputs, tb.inputs);
+        assert_eq!(decoded.outputs, tb.outputs);
+        assert_eq!(decoded.witnesses, tb.witnesses);
+        assert_eq!(decoded.to_json()?, json);
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_data_offsets_increase_across_inputs() -> Result<()> {
+        let given_a_predicate_with_data =
+            |data: Vec<Data>| given_a_predicate(vec![1; 8], UnresolvedBytes::new(data));
+        let inputs = vec![
+            given_a_predicate_with_data(vec![Data::Inline(vec![1; 16])]),
+            given_a_predicate_with_data(vec![Data::Dynamic(vec![Data::Inline(vec![2; 8])])]),
+            given_a_predicate_with_data(vec![Data::Inline(vec![3; 8])]),
+        ];
+
+        let resolved_data = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_inputs(inputs)
+            .resolved_predicate_data()?;
+
+        assert_eq!(resolved_data.len(), 3);
+        assert!(resolved_data
+            .windows(2)
+            .all(|pair| pair[0].0 + pair[0].1.len() < pair[1].0));
+
+        let (dynamic_data_offset, dynamic_data) = &resolved_data[1];
+        let pointer = u64::from_be_bytes(dynamic_data[..8].try_into().expect("has a pointer"));
+        assert_eq!(pointer as usize, dynamic_data_offset + 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_offsets_point_to_the_data_in_the_serialized_tx() -> Result<()> {
+        let given_a_predicate_with_data = |code_len: usize, data: Vec<u8>| {
+            given_a_predicate(
+                vec![1; code_len],
+                UnresolvedBytes::new(vec![Data::Inline(data)]),
+            )
+        };
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_script_data(vec![7; 12])
+            .with_inputs(vec![
+                given_a_predicate_with_data(8, vec![1; 16]),
+                given_a_predicate_with_data(13, vec![2; 8]),
+                given_a_predicate_with_data(4, vec![3; 24]),
+            ]);
+
+        let offsets = tb.predicate_offsets()?;
+
+        assert_eq!(offsets.len(), 3);
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            offsets[0],
+            tb.inputs_offset() + offsets::coin_predicate_data_offset(8)
+        );
+
+        let tx = tb.resolve_fuel_tx()?;
+        let tx_bytes = tx.to_bytes();
+        let tx_offset = tb.consensus_parameters().tx_params().tx_offset();
+        for (offset, input) in offsets.into_iter().zip(tx.inputs()) {
+            let (_, data, _) = input.predicate().expect("is a predicate");
+            let start = offset - tx_offset;
+            assert_eq!(&tx_bytes[start..start + data.len()], data);
         }
-    };
-}
 
-#[derive(Debug, Clone)]
-pub struct ScriptTransactionBuilder {
-    pub gas_price: Option<u64>,
-    pub gas_limit: Option<u64>,
-    pub witness_limit: Option<u64>,
-    pub max_fee: Option<u64>,
-    pub maturity: u32,
-    pub script: Vec<u8>,
-    pub script_data: Vec<u8>,
-    pub inputs: Vec<Input>,
-    pub outputs: Vec<Output>,
-    pub witnesses: Vec<Witness>,
-    pub gas_estimation_tolerance: f32,
-    pub(crate) network_info: NetworkInfo,
-    unresolved_signatures: UnresolvedSignatures,
-}
+        Ok(())
+    }
 
-#[derive(Debug, Clone)]
-pub struct CreateTransactionBuilder {
-    pub gas_price: Option<u64>,
-    pub maturity: u32,
-    pub witness_limit: Option<u64>,
-    pub max_fee: Option<u64>,
-    pub bytecode_length: u64,
-    pub bytecode_witness_index: u8,
-    pub storage_slots: Vec<StorageSlot>,
-    pub inputs: Vec<Input>,
-    pub outputs: Vec<Output>,
-    pub witnesses: Vec<Witness>,
-    pub salt: Salt,
-    pub(crate) network_info: NetworkInfo,
-    unresolved_signatures: UnresolvedSignatures,
-}
+    #[test]
+    fn predicate_tx_is_built_offline() -> Result<()> {
+        let tx = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_limit(1_000)
+            .with_inputs(vec![given_a_predicate_input(Some(42))])
+            .build_offline()?;
 
-impl_tx_trait!(ScriptTransactionBuilder, ScriptTransaction);
-impl_tx_trait!(CreateTransactionBuilder, CreateTransaction);
+        assert_eq!(tx.gas_limit(), 1_000);
+        assert_eq!(tx.inputs()[0].predicate_gas_used(), Some(42));
 
-impl ScriptTransactionBuilder {
-    pub fn new(network_info: NetworkInfo) -> ScriptTransactionBuilder {
-        ScriptTransactionBuilder {
-            gas_price: None,
-            gas_limit: None,
-            witness_limit: None,
-            max_fee: None,
-            maturity: 0,
-            script: vec![],
-            script_data: vec![],
-            inputs: vec![],
-            outputs: vec![],
-            witnesses: vec![],
-            network_info,
-            gas_estimation_tolerance: 0.05,
-            unresolved_signatures: Default::default(),
+        Ok(())
+    }
+
+    #[test]
+    fn offline_build_requires_predicate_gas_and_gas_limit() {
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![given_a_predicate_input(None)]);
+        let result = tb.clone().build_offline();
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("index `0`")));
+
+        let result = tb
+            .with_inputs(vec![given_a_predicate_input(Some(42))])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .build_offline();
+        assert!(
+            matches!(result, Err(Error::InvalidData(msg)) if msg.contains("`script_gas_limit`"))
+        );
+    }
+
+    struct MockResourceStream {
+        coins: Vec<Coin>,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ResourceStream for MockResourceStream {
+        async fn next_resource(&mut self) -> Result<Option<CoinType>> {
+            Ok((!self.coins.is_empty()).then(|| CoinType::Coin(self.coins.remove(0))))
         }
     }
 
-    async fn build(self, provider: impl DryRunner) -> Result<ScriptTransaction> {
-        let is_using_predicates = self.is_using_predicates();
-        let base_offset = if is_using_predicates {
-            self.base_offset()
-        } else {
-            0
+    struct MockMessageStatusQuerier {
+        spent_nonces: HashSet<Nonce>,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl MessageStatusQuerier for MockMessageStatusQuerier {
+        async fn message_status(&self, message: &Message) -> Result<MessageStatus> {
+            Ok(if self.spent_nonces.contains(&message.nonce) {
+                MessageStatus::Spent
+            } else {
+                MessageStatus::Unspent
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn spent_message_input_is_rejected() -> Result<()> {
+        let given_a_message_input = |nonce| {
+            Input::resource_signed(CoinType::Message(Message::new(
+                Bech32Address::default(),
+                Bech32Address::default(),
+                nonce,
+                100,
+                vec![],
+            )))
+        };
+        let spent_nonce = Nonce::new([2; 32]);
+        let querier = MockMessageStatusQuerier {
+            spent_nonces: HashSet::from([spent_nonce]),
         };
 
-        let num_witnesses = self.num_witnesses()?;
-        let tx = self
-            .resolve_fuel_tx_provider(base_offset, num_witnesses, &provider)
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![given_a_message_input(Nonce::new([1; 32]))]);
+        tb.validate_messages_spendable(&querier).await?;
+
+        let tb = tb.with_inputs(vec![
+            given_a_message_input(Nonce::new([1; 32])),
+            given_a_message_input(spent_nonce),
+        ]);
+        let result = tb.validate_messages_spendable(&querier).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.starts_with("message input at index `1`")
+        ));
+
+        Ok(())
+    }
+
+    struct MockResourceQuerier {
+        coin: Coin,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl ResourceQuerier for MockResourceQuerier {
+        async fn coin(&self, utxo_id: &UtxoId) -> Result<Option<Coin>> {
+            Ok((self.coin.utxo_id == *utxo_id).then(|| self.coin.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn coin_is_added_by_its_id() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let coin = Coin {
+            amount: 100,
+            utxo_id: UtxoId::new([1; 32].into(), 0),
+            owner: owner.clone(),
+            ..Default::default()
+        };
+        let querier = MockResourceQuerier { coin: coin.clone() };
+        let mut tb = ScriptTransactionBuilder::new(given_network_info());
+
+        tb.add_coin_by_id(coin.utxo_id, owner.clone(), secret_key, &querier)
             .await?;
+        let tx = tb.clone().build_offline()?;
 
-        Ok(ScriptTransaction {
-            tx,
-            is_using_predicates,
+        assert_eq!(
+            tb.inputs,
+            vec![Input::resource_signed(CoinType::Coin(coin))]
+        );
+        assert_eq!(tx.inputs()[0].witness_index(), Some(0));
+
+        let missing_coin = tb
+            .add_coin_by_id(UtxoId::new([2; 32].into(), 0), owner, secret_key, &querier)
+            .await;
+        assert!(matches!(
+            missing_coin,
+            Err(Error::InvalidData(msg)) if msg.ends_with("not found")
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn funding_from_stream_stops_at_coverage() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let coins: Vec<_> = (1..=5)
+            .map(|tx_id| Coin {
+                amount: 100,
+                asset_id: BASE_ASSET_ID,
+                utxo_id: UtxoId::new([tx_id; 32].into(), 0),
+                owner: owner.clone(),
+                ..Default::default()
+            })
+            .collect();
+        let mut stream = MockResourceStream { coins };
+        let given_a_transfer = |amount| {
+            let mut tb = ScriptTransactionBuilder::prepare_transfer(
+                vec![],
+                vec![Output::coin(Address::zeroed(), amount, BASE_ASSET_ID)],
+                TxPolicies::default(),
+                given_network_info(),
+            );
+            tb.add_unresolved_signature(owner.clone(), secret_key);
+            tb
+        };
+
+        let tb = given_a_transfer(250)
+            .fund_from_stream(&mut stream, &HashSet::new(), MockDryRunner::default())
+            .await?;
+
+        assert_eq!(tb.inputs.len(), 3);
+        assert_eq!(stream.coins.len(), 2);
+        tb.build_offline()?;
+
+        let result = given_a_transfer(1_000)
+            .fund_from_stream(&mut stream, &HashSet::new(), MockDryRunner::default())
+            .await;
+        assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("exhausted")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn funding_from_stream_skips_excluded_utxos_and_other_assets() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let coins: Vec<_> = [
+            BASE_ASSET_ID,
+            AssetId::from([1; 32]),
+            BASE_ASSET_ID,
+            BASE_ASSET_ID,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(tx_id, asset_id)| Coin {
+            amount: 100,
+            asset_id,
+            utxo_id: UtxoId::new([tx_id as u8; 32].into(), 0),
+            owner: owner.clone(),
+            ..Default::default()
         })
+        .collect();
+        let excluded_utxo = coins[0].utxo_id;
+        let mut stream = MockResourceStream {
+            coins: coins.clone(),
+        };
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![],
+            vec![Output::coin(Address::zeroed(), 150, BASE_ASSET_ID)],
+            TxPolicies::default(),
+            given_network_info(),
+        );
+        tb.add_unresolved_signature(owner, secret_key);
+        let tb = tb
+            .fund_from_stream(
+                &mut stream,
+                &HashSet::from([excluded_utxo]),
+                MockDryRunner::default(),
+            )
+            .await?;
+
+        let expected_inputs = coins[2..]
+            .iter()
+            .map(|coin| Input::resource_signed(CoinType::Coin(coin.clone())))
+            .collect::<Vec<_>>();
+        assert_eq!(tb.inputs, expected_inputs);
+        tb.build_offline()?;
+
+        Ok(())
     }
 
-    // When dry running a tx with `utxo_validation` off, the node will not validate signatures.
-    // However, the node will check if the right number of witnesses is present.
-    // This function will create empty witnesses such that the total length matches the expected one.
-    fn create_dry_run_witnesses(&self, num_witnesses: u8) -> Vec<Witness> {
-        let unresolved_witnesses_len = self.unresolved_signatures.addr_idx_offset_map.len();
-        repeat_with(Default::default)
-            // Add one in case there is no witnesses at all
-            .take(max(num_witnesses as usize + unresolved_witnesses_len, 1))
-            .collect()
+    #[tokio::test]
+    async fn funding_from_stream_requires_a_signer_for_every_owner() {
+        let mut stream = MockResourceStream {
+            coins: vec![Coin {
+                amount: 100,
+                asset_id: BASE_ASSET_ID,
+                owner: Bech32Address::new("fuel", [1; 32]),
+                ..Default::default()
+            }],
+        };
+
+        let result = ScriptTransactionBuilder::prepare_transfer(
+            vec![],
+            vec![Output::coin(Address::zeroed(), 50, BASE_ASSET_ID)],
+            TxPolicies::default(),
+            given_network_info(),
+        )
+        .fund_from_stream(&mut stream, &HashSet::new(), MockDryRunner::default())
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.starts_with("signature missing")
+        ));
     }
 
-    // When the `script_gas_limit` was not set by the user, `dry_run` the tx
-    // and set the `script_gas_limit` to the actual `gas_used`
-    async fn set_script_gas_limit_to_gas_used(
-        tx: &mut Script,
-        provider: &impl DryRunner,
-        network_info: &NetworkInfo,
-        tolerance: f32,
-    ) -> Result<()> {
-        let consensus_params = &network_info.consensus_parameters;
-        // Add `1` because of rounding
-        let max_gas = tx.max_gas(consensus_params.gas_costs(), consensus_params.fee_params()) + 1;
+    #[test]
+    fn auto_funded_input_index_has_to_fit_a_u8() -> Result<()> {
+        let mut tb = ScriptTransactionBuilder::new(given_network_info());
+        for _ in 0..=u8::MAX {
+            tb = tb.with_auto_funded_input(given_a_secret_key(1), BASE_ASSET_ID)?;
+        }
 
-        // TODO: @xgreenx why do I need to / 2
-        tx.set_script_gas_limit((network_info.max_gas_per_tx() / 2) - max_gas);
+        let err = tb
+            .with_auto_funded_input(given_a_secret_key(1), BASE_ASSET_ID)
+            .expect_err("should fail because of the input index");
 
-        // The `dry_run` validation will check if there is an input present that can cover
-        // the tx fees. If we are estimating without inputs we have to add a temporary one
-        tx.inputs_mut().push(FuelInput::coin_signed(
-            Default::default(),
-            Default::default(),
-            1_000_000_000,
-            Default::default(),
-            TxPointer::default(),
-            0,
-            0u32.into(),
+        let Error::InvalidData(msg) = err else {
+            panic!("expected invalid data error")
+        };
+        assert_eq!(msg, "auto funded input can not be added after `256` inputs");
+
+        Ok(())
+    }
+
+    #[test]
+    fn signatures_are_verified_against_the_expected_owners() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Address::from(*PublicKey::from(&secret_key).hash());
+        let chain_id = given_network_info().chain_id();
+        let mut tx = given_a_base_asset_transfer(vec![]).build_offline()?;
+
+        verify_signatures(&tx, chain_id, &[(0, owner)])?;
+
+        let other_message = CryptoMessage::new(b"not the tx id");
+        tx.tx.witnesses_mut()[0] =
+            Witness::from(Signature::sign(&secret_key, &other_message).as_ref());
+        let result = verify_signatures(&tx, chain_id, &[(0, owner)]);
+
+        assert!(matches!(result, Err(Error::InvalidData(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn predicate_funding_an_uninvolved_asset_is_rejected() {
+        let asset_id = AssetId::new([1; 32]);
+        let coin = Coin {
+            amount: 100,
+            asset_id,
+            ..Default::default()
+        };
+        let predicate_input =
+            Input::resource_predicate(CoinType::Coin(coin), vec![1, 2, 3], Default::default());
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![predicate_input])
+            .with_outputs(vec![Output::change(Address::zeroed(), 0, BASE_ASSET_ID)]);
+
+        assert!(matches!(
+            tb.validate_predicate_assets(),
+            Err(BuildError::UninvolvedPredicateAsset { input_idx: 0, asset_id: id }) if id == asset_id
         ));
 
-        let gas_used = provider
-            .dry_run_and_get_used_gas(tx.clone().into(), tolerance)
-            .await?;
+        let tb = tb.with_outputs(vec![Output::change(Address::zeroed(), 0, asset_id)]);
+        assert!(tb.validate_predicate_assets().is_ok());
+    }
+
+    #[test]
+    fn immature_coin_requires_a_high_enough_tx_maturity() -> Result<()> {
+        let owner = Bech32Address::default();
+        let coin = Coin {
+            amount: 100,
+            maturity: 10,
+            owner: owner.clone(),
+            ..Default::default()
+        };
+        let mut tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_inputs(vec![Input::resource_signed(CoinType::Coin(coin))])
+            .with_maturity(9);
+        tb.add_unresolved_signature(owner, given_a_secret_key(1));
+
+        assert!(matches!(
+            tb.build_errors()[..],
+            [BuildError::ImmatureCoin {
+                input_idx: 0,
+                coin_maturity: 10,
+                tx_maturity: 9,
+            }]
+        ));
+        assert!(matches!(
+            tb.clone().build_offline(),
+            Err(Error::InvalidData(_))
+        ));
+
+        tb.with_maturity(10).build_offline()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn all_coin_inputs_share_the_set_tx_pointer() -> Result<()> {
+        let tx_pointer = TxPointer::new(10.into(), 3);
+        let mut tb = given_a_base_asset_transfer(vec![]).with_tx_pointer(tx_pointer);
+        tb.inputs_mut().push(given_a_predicate_input(Some(0)));
+
+        let tx = tb.resolve_fuel_tx()?;
+
+        assert_eq!(tx.inputs().len(), 2);
+        assert!(tx
+            .inputs()
+            .iter()
+            .all(|input| input.tx_pointer() == Some(&tx_pointer)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn gas_costs_override_changes_the_estimated_max_gas() -> Result<()> {
+        let mut tb = given_a_base_asset_transfer(vec![]);
+        // With a gas price of `1` the fee is equal to the gas
+        tb.network_info.consensus_parameters.fee_params = FeeParameters {
+            gas_price_factor: 1,
+            ..Default::default()
+        };
+        let overridden_tb = tb.clone().with_gas_costs_override(GasCosts::free());
+
+        let max_gas = tb.fee_at_gas_price(1, 0)?.max_fee();
+        let overridden_max_gas = overridden_tb.fee_at_gas_price(1, 0)?.max_fee();
+
+        assert!(overridden_max_gas < max_gas);
+        assert_eq!(
+            overridden_tb.consensus_parameters().gas_costs,
+            tb.consensus_parameters().gas_costs
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn previewed_signatures_match_the_built_tx() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![]);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&given_a_secret_key(1)).hash());
+
+        let preview = tb.preview_signature_witnesses(given_network_info().chain_id())?;
+        let tx = tb.build_offline()?;
+
+        assert_eq!(preview, vec![(owner, tx.witnesses()[0].clone())]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn signatures_of_external_signers_can_not_be_previewed() {
+        let secret_key = given_a_secret_key(2);
+        let mut tb = given_a_base_asset_transfer(vec![]);
+        tb.add_external_signer(MockPasskeySigner {
+            address: Bech32Address::new("fuel", PublicKey::from(&secret_key).hash()),
+            secret_key,
+        });
+
+        let result = tb.preview_signature_witnesses(given_network_info().chain_id());
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidData(msg)) if msg.contains("external signers")
+        ));
+    }
+
+    #[test]
+    fn fee_scales_with_the_gas_price() -> Result<()> {
+        let mut tb = given_a_base_asset_transfer(vec![]);
+        tb.network_info.consensus_parameters.fee_params = FeeParameters {
+            gas_price_factor: 1,
+            ..Default::default()
+        };
+
+        let fee = tb.fee_at_gas_price(10, 1_000)?;
+        let raised_fee = tb.fee_at_gas_price(20, 1_000)?;
+
+        assert!(fee.min_fee() > 0);
+        assert_eq!(raised_fee.min_fee(), 2 * fee.min_fee());
+        assert_eq!(raised_fee.max_fee(), 2 * fee.max_fee());
+
+        Ok(())
+    }
 
-        // Remove the temporary coin
-        tx.inputs_mut().pop();
+    #[test]
+    fn fee_tiers_increase_with_the_gas_price() -> Result<()> {
+        let mut tb = given_a_base_asset_transfer(vec![]);
+        tb.network_info.consensus_parameters.fee_params = FeeParameters {
+            gas_price_factor: 1,
+            ..Default::default()
+        };
 
-        tx.set_script_gas_limit(gas_used);
+        let tiers = tb.fee_tiers(&[1, 5, 10], 1_000)?;
+
+        assert_eq!(tiers.len(), 3);
+        assert!(tiers
+            .windows(2)
+            .all(|pair| pair[0].max_fee() < pair[1].max_fee()));
+        assert_eq!(
+            tiers[2].max_fee(),
+            tb.fee_at_gas_price(10, 1_000)?.max_fee()
+        );
 
         Ok(())
     }
 
-    async fn resolve_fuel_tx_provider(
-        self,
-        base_offset: usize,
-        num_witnesses: u8,
-        provider: &impl DryRunner,
-    ) -> Result<Script> {
-        let mut policies = self.generate_shared_fuel_policies();
-        policies.set(
-            PolicyType::WitnessLimit,
-            self.witness_limit.or(Some(DEFAULT_SCRIPT_WITNESS_LIMIT)),
-        );
+    #[tokio::test]
+    async fn fee_check_matches_the_fee_of_the_built_tx() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_witnesses(vec![Witness::from(vec![1u8; 16])]);
+        let dry_runner = MockDryRunner { gas_used: 100 };
 
-        let has_no_code = self.script.is_empty();
-        let dry_run_witnesses = self.create_dry_run_witnesses(num_witnesses);
-        let mut tx = FuelTransaction::script(
-            0, // default value - will be overwritten
-            self.script,
-            self.script_data,
-            policies,
-            resolve_fuel_inputs(
-                self.inputs,
-                base_offset + policies.size_dynamic(),
-                num_witnesses,
-                &self.unresolved_signatures,
-            )?,
-            self.outputs,
-            dry_run_witnesses,
-        );
+        let fee = tb
+            .fee_checked_from_tx(dry_runner)
+            .await?
+            .expect("should compute the fee");
 
-        if has_no_code {
-            tx.set_script_gas_limit(0);
-        // Use the user defined value even if it makes the tx revert
-        } else if let Some(gas_limit) = self.gas_limit {
-            tx.set_script_gas_limit(gas_limit);
-        } else {
-            Self::set_script_gas_limit_to_gas_used(
-                &mut tx,
-                provider,
-                &self.network_info,
-                self.gas_estimation_tolerance,
-            )
-            .await?;
-        }
+        let tx = tb.clone().build(&dry_runner).await?;
+        let built_fee = TransactionFee::checked_from_tx(
+            tb.consensus_parameters().gas_costs(),
+            tb.consensus_parameters().fee_params(),
+            &tx.tx,
+        )
+        .expect("should compute the fee");
+        assert_eq!(fee.min_fee(), built_fee.min_fee());
+        assert_eq!(fee.max_fee(), built_fee.max_fee());
 
-        let missing_witnesses = generate_missing_witnesses(
-            tx.id(&self.network_info.chain_id()),
-            &self.unresolved_signatures,
-        );
-        *tx.witnesses_mut() = [self.witnesses, missing_witnesses].concat();
+        Ok(())
+    }
 
-        Ok(tx)
+    struct MockRateProvider {
+        rate: f64,
     }
 
-    fn base_offset(&self) -> usize {
-        offsets::base_offset_script(self.consensus_parameters())
-            + padded_len_usize(self.script_data.len())
-            + padded_len_usize(self.script.len())
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl RateProvider for MockRateProvider {
+        async fn rate(&self, _: AssetId, _: AssetId) -> Result<f64> {
+            Ok(self.rate)
+        }
     }
 
-    pub fn with_script(mut self, script: Vec<u8>) -> Self {
-        self.script = script;
-        self
+    struct MockGasPriceOracle {
+        gas_price: u64,
     }
 
-    pub fn with_script_data(mut self, script_data: Vec<u8>) -> Self {
-        self.script_data = script_data;
-        self
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl GasPriceOracle for MockGasPriceOracle {
+        async fn suggested_gas_price(&self) -> Result<u64> {
+            Ok(self.gas_price)
+        }
     }
 
-    pub fn with_gas_estimation_tolerance(mut self, tolerance: f32) -> Self {
-        self.gas_estimation_tolerance = tolerance;
-        self
+    #[tokio::test]
+    async fn gas_price_is_taken_from_the_oracle() -> Result<()> {
+        let oracle = MockGasPriceOracle { gas_price: 7 };
+
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_oracle_gas_price(&oracle)
+            .await?;
+
+        assert_eq!(tb.gas_price, Some(7));
+        assert_eq!(tb.build_offline()?.gas_price(), 7);
+
+        Ok(())
     }
 
-    pub fn prepare_transfer(
-        inputs: Vec<Input>,
-        outputs: Vec<Output>,
-        tx_policies: TxPolicies,
-        network_info: NetworkInfo,
-    ) -> Self {
-        ScriptTransactionBuilder::new(network_info)
-            .with_inputs(inputs)
-            .with_outputs(outputs)
-            .with_tx_policies(tx_policies)
+    #[derive(Debug)]
+    struct FixedFeeCalculator;
+
+    impl FeeCalculator for FixedFeeCalculator {
+        fn fee(
+            &self,
+            _: &FuelTransaction,
+            _: &GasCosts,
+            _: &FeeParameters,
+        ) -> Option<TransactionFee> {
+            Some(TransactionFee::new(10, 20, 10, 20))
+        }
     }
 
-    /// Craft a transaction used to transfer funds to a contract.
-    pub fn prepare_contract_transfer(
-        to: ContractId,
-        amount: u64,
-        asset_id: AssetId,
-        inputs: Vec<Input>,
-        outputs: Vec<Output>,
-        tx_policies: TxPolicies,
-        network_info: NetworkInfo,
-    ) -> Self {
-        let script_data: Vec<u8> = [
-            to.to_vec(),
-            amount.to_be_bytes().to_vec(),
-            asset_id.to_vec(),
-        ]
-        .into_iter()
-        .flatten()
-        .collect();
+    #[tokio::test]
+    async fn custom_fee_calculator_is_used_for_the_estimations() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![]).with_fee_calculator(FixedFeeCalculator);
 
-        // This script loads:
-        //  - a pointer to the contract id,
-        //  - the actual amount
-        //  - a pointer to the asset id
-        // into the registers 0x10, 0x12, 0x13
-        // and calls the TR instruction
-        let script = vec![
-            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
-            op::addi(0x11, 0x10, ContractId::LEN as u16),
-            op::lw(0x12, 0x11, 0),
-            op::addi(0x13, 0x11, WORD_SIZE as u16),
-            op::tr(0x10, 0x12, 0x13),
-            op::ret(RegId::ONE),
-        ]
-        .into_iter()
-        .collect();
+        let fee = tb
+            .fee_checked_from_tx(MockDryRunner::default())
+            .await?
+            .expect("should compute the fee");
+        let fee_at_gas_price = tb.fee_at_gas_price(1, 0)?;
 
-        ScriptTransactionBuilder::new(network_info)
-            .with_script(script)
-            .with_script_data(script_data)
-            .with_inputs(inputs)
-            .with_outputs(outputs)
-            .with_tx_policies(tx_policies)
+        assert_eq!((fee.min_fee(), fee.max_fee()), (10, 20));
+        assert_eq!(fee_at_gas_price.max_fee(), 20);
+
+        Ok(())
     }
 
-    /// Craft a transaction used to transfer funds to the base chain.
-    pub fn prepare_message_to_output(
-        to: Address,
-        amount: u64,
-        inputs: Vec<Input>,
-        tx_policies: TxPolicies,
-        network_info: NetworkInfo,
-    ) -> Self {
-        let script_data: Vec<u8> = [to.to_vec(), amount.to_be_bytes().to_vec()]
-            .into_iter()
-            .flatten()
-            .collect();
+    #[tokio::test]
+    async fn fee_is_converted_to_the_target_asset() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_price(1);
+        let dry_runner = MockDryRunner { gas_used: 100 };
+        let rate_provider = MockRateProvider { rate: 2.5 };
+        let max_fee = tb
+            .fee_checked_from_tx(dry_runner)
+            .await?
+            .expect("should compute the fee")
+            .max_fee();
 
-        // This script loads:
-        //  - a pointer to the recipient address,
-        //  - the amount
-        // into the registers 0x10, 0x11
-        // and calls the SMO instruction
-        let script: Vec<u8> = vec![
-            op::gtf(0x10, 0x00, GTFArgs::ScriptData.into()),
-            op::addi(0x11, 0x10, Bytes32::LEN as u16),
-            op::lw(0x11, 0x11, 0),
-            op::smo(0x10, 0x00, 0x00, 0x11),
-            op::ret(RegId::ONE),
-        ]
-        .into_iter()
-        .collect();
+        let fee = tb
+            .fee_in_asset(AssetId::from([1u8; 32]), &rate_provider, dry_runner)
+            .await?;
+        let base_asset_fee = tb
+            .fee_in_asset(BASE_ASSET_ID, &rate_provider, dry_runner)
+            .await?;
 
-        let outputs = vec![Output::change(to, 0, BASE_ASSET_ID)];
+        assert_eq!(fee, (max_fee as f64 * 2.5).ceil() as u64);
+        assert_eq!(base_asset_fee, max_fee);
 
-        ScriptTransactionBuilder::new(network_info)
-            .with_tx_policies(tx_policies)
-            .with_script(script)
-            .with_script_data(script_data)
-            .with_inputs(inputs)
-            .with_outputs(outputs)
+        Ok(())
     }
 
-    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
-        self.gas_limit = Some(gas_limit);
-        self
-    }
+    #[tokio::test]
+    async fn summary_reflects_the_tx() -> Result<()> {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let recipient = Address::from([2u8; 32]);
+        let asset_id = AssetId::from([3u8; 32]);
+        let coins =
+            [(BASE_ASSET_ID, 100), (asset_id, 30), (asset_id, 20)].map(|(asset_id, amount)| {
+                Input::resource_signed(CoinType::Coin(Coin {
+                    amount,
+                    asset_id,
+                    owner: owner.clone(),
+                    ..Default::default()
+                }))
+            });
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            coins.to_vec(),
+            vec![
+                Output::coin(recipient, 40, asset_id),
+                Output::change((&owner).into(), 0, asset_id),
+                Output::change((&owner).into(), 0, BASE_ASSET_ID),
+            ],
+            TxPolicies::default().with_gas_price(1).with_maturity(3),
+            given_network_info(),
+        );
+        tb.add_unresolved_signature(owner.clone(), secret_key);
+        let dry_runner = MockDryRunner { gas_used: 100 };
 
-    fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
-        self.gas_limit = tx_policies.script_gas_limit();
-        self.gas_price = tx_policies.gas_price();
-        self.witness_limit = tx_policies.witness_limit();
-        self.max_fee = tx_policies.max_fee();
-        self.maturity = tx_policies.maturity();
+        let summary = tb.summary(&dry_runner).await?;
 
-        self
-    }
-}
+        let fee = tb
+            .fee_checked_from_tx(dry_runner)
+            .await?
+            .expect("should compute the fee");
+        let tx = tb.clone().build(&dry_runner).await?;
+        assert_eq!(
+            summary.input_amounts,
+            BTreeMap::from([(BASE_ASSET_ID, 100), (asset_id, 50)])
+        );
+        assert_eq!(
+            summary.outputs,
+            vec![OutputSummary {
+                to: recipient,
+                asset_id,
+                amount: 40
+            }]
+        );
+        assert_eq!(
+            summary.change_recipients,
+            BTreeMap::from([
+                (BASE_ASSET_ID, Address::from(&owner)),
+                (asset_id, Address::from(&owner))
+            ])
+        );
+        assert_eq!(summary.max_fee, fee.max_fee());
+        assert_eq!(summary.max_gas, tx.max_gas(tb.consensus_parameters()));
+        assert_eq!(&summary.policies, tx.tx.policies());
+        assert_eq!(summary.signers, vec![Address::from(&owner)]);
 
-impl CreateTransactionBuilder {
-    fn new(network_info: NetworkInfo) -> CreateTransactionBuilder {
-        CreateTransactionBuilder {
-            gas_price: None,
-            witness_limit: None,
-            max_fee: None,
-            maturity: 0,
-            bytecode_length: 0,
-            bytecode_witness_index: 0,
-            storage_slots: vec![],
-            salt: Default::default(),
-            inputs: vec![],
-            outputs: vec![],
-            witnesses: vec![],
-            network_info,
-            unresolved_signatures: Default::default(),
-        }
+        Ok(())
     }
 
-    pub fn build(self) -> Result<CreateTransaction> {
-        let is_using_predicates = self.is_using_predicates();
-        let base_offset = if is_using_predicates {
-            self.base_offset()
-        } else {
-            0
-        };
+    #[tokio::test]
+    async fn change_amount_is_predicted() -> Result<()> {
+        let tb = given_a_base_asset_transfer(vec![
+            Output::coin(Address::zeroed(), 300, BASE_ASSET_ID),
+            Output::change(Address::zeroed(), 0, BASE_ASSET_ID),
+        ]);
 
-        let num_witnesses = self.num_witnesses()?;
-        let tx = self.resolve_fuel_tx(base_offset, num_witnesses)?;
+        let fee = tb
+            .fee_checked_from_tx(MockDryRunner::default())
+            .await?
+            .expect("fee should not overflow");
+        let change = tb.predicted_change_amount(MockDryRunner::default()).await?;
 
-        Ok(CreateTransaction {
-            tx,
-            is_using_predicates,
-        })
+        assert!(fee.max_fee() > 0);
+        assert_eq!(change, 1_000 - 300 - fee.max_fee());
+
+        Ok(())
     }
 
-    fn resolve_fuel_tx(self, mut base_offset: usize, num_witnesses: u8) -> Result<Create> {
-        let mut policies = self.generate_shared_fuel_policies();
-        policies.set(
-            PolicyType::WitnessLimit,
-            self.witness_limit.or(Some(DEFAULT_CREATE_WITNESS_LIMIT)),
-        );
+    #[tokio::test]
+    async fn funding_covers_the_outputs_and_the_fee() -> Result<()> {
+        // The amount of the output does not change the fee
+        let fee =
+            given_a_base_asset_transfer(vec![Output::coin(Address::zeroed(), 0, BASE_ASSET_ID)])
+                .fee_checked_from_tx(MockDryRunner::default())
+                .await?
+                .expect("fee should not overflow")
+                .max_fee();
 
-        let storage_slots_offset = self.storage_slots.len() * StorageSlot::SLOT_SIZE;
-        base_offset += storage_slots_offset + policies.size_dynamic();
+        let funded = given_a_base_asset_transfer(vec![Output::coin(
+            Address::zeroed(),
+            1_000 - fee,
+            BASE_ASSET_ID,
+        )]);
+        assert!(funded.is_fully_funded(MockDryRunner::default()).await?);
 
-        let mut tx = FuelTransaction::create(
-            self.bytecode_witness_index,
-            policies,
-            self.salt,
-            self.storage_slots,
-            resolve_fuel_inputs(
-                self.inputs,
-                base_offset,
-                num_witnesses,
-                &self.unresolved_signatures,
-            )?,
-            self.outputs,
-            self.witnesses,
+        let short_on_fee = given_a_base_asset_transfer(vec![Output::coin(
+            Address::zeroed(),
+            1_000 - fee + 1,
+            BASE_ASSET_ID,
+        )]);
+        assert!(
+            !short_on_fee
+                .is_fully_funded(MockDryRunner::default())
+                .await?
         );
 
-        let missing_witnesses = generate_missing_witnesses(
-            tx.id(&self.network_info.chain_id()),
-            &self.unresolved_signatures,
+        let short_on_asset = given_a_base_asset_transfer(vec![Output::coin(
+            Address::zeroed(),
+            1,
+            AssetId::from([1u8; 32]),
+        )]);
+        assert!(
+            !short_on_asset
+                .is_fully_funded(MockDryRunner::default())
+                .await?
         );
-        tx.witnesses_mut().extend(missing_witnesses);
 
-        Ok(tx)
+        Ok(())
     }
 
-    fn base_offset(&self) -> usize {
-        offsets::base_offset_create(self.consensus_parameters())
+    #[tokio::test]
+    async fn change_amount_requires_a_single_change_output() {
+        for outputs in [
+            vec![Output::coin(Address::zeroed(), 300, BASE_ASSET_ID)],
+            vec![
+                Output::change(Address::zeroed(), 0, BASE_ASSET_ID),
+                Output::change(Address::zeroed(), 0, BASE_ASSET_ID),
+            ],
+        ] {
+            let result = given_a_base_asset_transfer(outputs)
+                .predicted_change_amount(MockDryRunner::default())
+                .await;
+
+            assert!(matches!(result, Err(Error::InvalidData(msg)) if msg.contains("exactly one")));
+        }
     }
 
-    pub fn with_bytecode_length(mut self, bytecode_length: u64) -> Self {
-        self.bytecode_length = bytecode_length;
-        self
+    #[derive(Default)]
+    struct MockDetailedDryRunner {
+        receipts: Vec<Receipt>,
+        storage_changes: Vec<StorageChange>,
+        count: AtomicUsize,
+        validated_count: AtomicUsize,
     }
 
-    pub fn with_bytecode_witness_index(mut self, bytecode_witness_index: u8) -> Self {
-        self.bytecode_witness_index = bytecode_witness_index;
-        self
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl DryRunner for MockDetailedDryRunner {
+        async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(0)
+        }
     }
 
-    pub fn with_storage_slots(mut self, mut storage_slots: Vec<StorageSlot>) -> Self {
-        // Storage slots have to be sorted otherwise we'd get a `TransactionCreateStorageSlotOrder`
-        // error.
-        storage_slots.sort();
-        self.storage_slots = storage_slots;
-        self
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl DetailedDryRunner for MockDetailedDryRunner {
+        async fn dry_run_and_get_receipts(&self, _: FuelTransaction) -> Result<Vec<Receipt>> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(self.receipts.clone())
+        }
+
+        async fn dry_run_and_get_receipts_with_utxo_validation(
+            &self,
+            _: FuelTransaction,
+        ) -> Result<Vec<Receipt>> {
+            self.validated_count.fetch_add(1, Ordering::SeqCst);
+            Ok(self.receipts.clone())
+        }
+
+        async fn dry_run_and_get_storage_changes(
+            &self,
+            _: FuelTransaction,
+        ) -> Result<Vec<StorageChange>> {
+            Ok(self.storage_changes.clone())
+        }
     }
 
-    pub fn with_salt(mut self, salt: impl Into<Salt>) -> Self {
-        self.salt = salt.into();
-        self
+    #[tokio::test]
+    async fn storage_changes_are_previewed() -> Result<()> {
+        let storage_changes = vec![
+            StorageChange {
+                contract_id: ContractId::new([1; 32]),
+                key: Bytes32::new([2; 32]),
+                old_value: None,
+                new_value: Some(Bytes32::new([3; 32])),
+            },
+            StorageChange {
+                contract_id: ContractId::new([1; 32]),
+                key: Bytes32::new([4; 32]),
+                old_value: Some(Bytes32::new([5; 32])),
+                new_value: None,
+            },
+        ];
+        let dry_runner = MockDetailedDryRunner {
+            storage_changes: storage_changes.clone(),
+            ..Default::default()
+        };
+
+        let previewed = given_a_base_asset_transfer(vec![])
+            .preview_storage_changes(&dry_runner)
+            .await?;
+
+        assert_eq!(previewed, storage_changes);
+
+        Ok(())
     }
 
-    pub fn prepare_contract_deployment(
-        binary: Vec<u8>,
-        contract_id: ContractId,
-        state_root: Bytes32,
-        salt: Salt,
-        storage_slots: Vec<StorageSlot>,
-        tx_policies: TxPolicies,
-        network_info: NetworkInfo,
-    ) -> Self {
-        let bytecode_witness_index = 0;
-        let outputs = vec![Output::contract_created(contract_id, state_root)];
-        let witnesses = vec![binary.into()];
+    #[tokio::test]
+    async fn simulation_parses_receipts() -> Result<()> {
+        let log = Receipt::log(Default::default(), 1, 0, 0, 0, 0, 0);
+        let log_data = Receipt::log_data(Default::default(), 0, 0, 0, 0, 0, vec![1, 2]);
+        let return_data = Receipt::return_data(Default::default(), 0, 0, 0, vec![3, 4]);
+        let script_result = Receipt::script_result(ScriptExecutionResult::Success, 42);
+        let receipts = vec![log.clone(), log_data.clone(), return_data, script_result];
 
-        CreateTransactionBuilder::new(network_info)
-            .with_tx_policies(tx_policies)
-            .with_bytecode_witness_index(bytecode_witness_index)
-            .with_salt(salt)
-            .with_storage_slots(storage_slots)
-            .with_outputs(outputs)
-            .with_witnesses(witnesses)
+        let result = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_limit(1_000)
+            .simulate(MockDetailedDryRunner {
+                receipts: receipts.clone(),
+                ..Default::default()
+            })
+            .await?;
+
+        assert_eq!(
+            result,
+            SimulationResult {
+                receipts,
+                return_data: Some(vec![3, 4]),
+                logs: vec![log, log_data],
+                gas_used: 42,
+            }
+        );
+
+        Ok(())
     }
 
-    fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
-        self.gas_price = tx_policies.gas_price();
-        self.witness_limit = tx_policies.witness_limit();
-        self.max_fee = tx_policies.max_fee();
-        self.maturity = tx_policies.maturity();
+    #[tokio::test]
+    async fn simulate_and_build_dry_runs_once() -> Result<()> {
+        let dry_runner = MockDetailedDryRunner {
+            receipts: vec![Receipt::script_result(ScriptExecutionResult::Success, 100)],
+            ..Default::default()
+        };
 
-        self
+        let (tx, simulation) = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_estimation_tolerance(0.1)
+            .simulate_and_build(&dry_runner)
+            .await?;
+
+        assert_eq!(dry_runner.count.load(Ordering::SeqCst), 1);
+        assert_eq!(simulation.gas_used, 100);
+        assert_eq!(*tx.tx.script_gas_limit(), 110);
+
+        Ok(())
     }
-}
 
-/// Resolve SDK Inputs to fuel_tx Inputs. This function will calculate the right
-/// data offsets for predicates and set witness indexes for signed coins.
-fn resolve_fuel_inputs(
-    inputs: Vec<Input>,
-    mut data_offset: usize,
-    num_witnesses: u8,
-    unresolved_signatures: &UnresolvedSignatures,
-) -> Result<Vec<FuelInput>> {
-    inputs
-        .into_iter()
-        .map(|input| match input {
-            Input::ResourceSigned { resource } => resolve_signed_resource(
-                resource,
-                &mut data_offset,
-                num_witnesses,
-                unresolved_signatures,
-            ),
-            Input::ResourcePredicate {
-                resource,
-                code,
-                data,
-            } => resolve_predicate_resource(resource, code, data, &mut data_offset),
-            Input::Contract {
-                utxo_id,
-                balance_root,
-                state_root,
-                tx_pointer,
-                contract_id,
-            } => {
-                data_offset += offsets::contract_input_offset();
-                Ok(FuelInput::contract(
-                    utxo_id,
-                    balance_root,
-                    state_root,
-                    tx_pointer,
-                    contract_id,
-                ))
-            }
-        })
-        .collect()
-}
+    #[tokio::test]
+    async fn simulate_and_build_honors_utxo_validation() -> Result<()> {
+        let dry_runner = MockDetailedDryRunner {
+            receipts: vec![Receipt::script_result(ScriptExecutionResult::Success, 100)],
+            ..Default::default()
+        };
 
-fn resolve_signed_resource(
-    resource: CoinType,
-    data_offset: &mut usize,
-    num_witnesses: u8,
-    unresolved_signatures: &UnresolvedSignatures,
-) -> Result<FuelInput> {
-    match resource {
-        CoinType::Coin(coin) => {
-            *data_offset += offsets::coin_signed_data_offset();
-            let owner = &coin.owner;
+        let (tx, _) = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_gas_estimation_tolerance(0.1)
+            .with_dry_run_utxo_validation(true)
+            .simulate_and_build(&dry_runner)
+            .await?;
 
-            unresolved_signatures
-                .addr_idx_offset_map
-                .get(owner)
-                .ok_or(error!(
-                    InvalidData,
-                    "signature missing for coin with owner: `{owner:?}`"
-                ))
-                .map(|witness_idx_offset| {
-                    create_coin_input(coin, num_witnesses + *witness_idx_offset as u8)
-                })
+        assert_eq!(dry_runner.count.load(Ordering::SeqCst), 0);
+        assert_eq!(dry_runner.validated_count.load(Ordering::SeqCst), 1);
+        assert_eq!(*tx.tx.script_gas_limit(), 110);
+
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct CapturingDryRunner {
+        txs: Mutex<Vec<FuelTransaction>>,
+        validated_txs: Mutex<Vec<FuelTransaction>>,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl DryRunner for CapturingDryRunner {
+        async fn dry_run_and_get_used_gas(&self, tx: FuelTransaction, _: f32) -> Result<u64> {
+            self.txs.lock().expect("poisoned lock").push(tx);
+            Ok(100)
         }
-        CoinType::Message(message) => {
-            *data_offset += offsets::message_signed_data_offset(message.data.len());
-            let recipient = &message.recipient;
 
-            unresolved_signatures
-                .addr_idx_offset_map
-                .get(recipient)
-                .ok_or(error!(
-                    InvalidData,
-                    "signature missing for message with recipient: `{recipient:?}`"
-                ))
-                .map(|witness_idx_offset| {
-                    create_coin_message_input(message, num_witnesses + *witness_idx_offset as u8)
-                })
+        async fn dry_run_and_get_used_gas_with_utxo_validation(
+            &self,
+            tx: FuelTransaction,
+            _: f32,
+        ) -> Result<u64> {
+            self.validated_txs.lock().expect("poisoned lock").push(tx);
+            Ok(100)
         }
     }
-}
 
-fn resolve_predicate_resource(
-    resource: CoinType,
-    code: Vec<u8>,
-    data: UnresolvedBytes,
-    data_offset: &mut usize,
-) -> Result<FuelInput> {
-    match resource {
-        CoinType::Coin(coin) => {
-            *data_offset += offsets::coin_predicate_data_offset(code.len());
+    #[tokio::test]
+    async fn utxo_validated_dry_run_receives_signed_tx() -> Result<()> {
+        let dry_runner = CapturingDryRunner::default();
+        let tb = given_a_base_asset_transfer(vec![])
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .with_dry_run_utxo_validation(true);
+        let owner_public_key = PublicKey::from(&given_a_secret_key(1));
 
-            let data = data.resolve(*data_offset as u64);
-            *data_offset += data.len();
+        let tx = tb.build(&dry_runner).await?;
 
-            let asset_id = coin.asset_id;
-            Ok(create_coin_predicate(coin, asset_id, code, data))
-        }
-        CoinType::Message(message) => {
-            *data_offset += offsets::message_predicate_data_offset(message.data.len(), code.len());
+        assert!(dry_runner.txs.lock().expect("poisoned lock").is_empty());
+        let validated_txs = dry_runner.validated_txs.lock().expect("poisoned lock");
+        let [FuelTransaction::Script(dry_run_tx)] = validated_txs.as_slice() else {
+            panic!("expected a single validated script dry run")
+        };
 
-            let data = data.resolve(*data_offset as u64);
-            *data_offset += data.len();
+        // No temporary coin is added and the witness is a valid signature
+        assert_eq!(dry_run_tx.inputs().len(), 1);
+        let bytes = <[u8; Signature::LEN]>::try_from(dry_run_tx.witnesses()[0].as_ref())
+            .expect("should be a signature");
+        let signature = Signature::from_bytes(bytes);
+        let message = CryptoMessage::from_bytes(*dry_run_tx.id(&ChainId::default()));
+        assert!(signature.verify(&owner_public_key, &message).is_ok());
 
-            Ok(create_coin_message_predicate(message, code, data))
-        }
+        assert_eq!(*tx.tx.script_gas_limit(), 100);
+
+        Ok(())
     }
-}
 
-pub fn create_coin_input(coin: Coin, witness_index: u8) -> FuelInput {
-    FuelInput::coin_signed(
-        coin.utxo_id,
-        coin.owner.into(),
-        coin.amount,
-        coin.asset_id,
-        TxPointer::default(),
-        witness_index,
-        0u32.into(),
-    )
-}
+    #[tokio::test]
+    async fn temporary_estimation_coin_holds_the_base_asset() -> Result<()> {
+        let dry_runner = CapturingDryRunner::default();
 
-pub fn create_coin_message_input(message: Message, witness_index: u8) -> FuelInput {
-    if message.data.is_empty() {
-        FuelInput::message_coin_signed(
-            message.sender.into(),
-            message.recipient.into(),
-            message.amount,
-            message.nonce,
-            witness_index,
-        )
-    } else {
-        FuelInput::message_data_signed(
-            message.sender.into(),
-            message.recipient.into(),
-            message.amount,
-            message.nonce,
-            witness_index,
-            message.data,
-        )
-    }
-}
+        let tx = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+            .build(&dry_runner)
+            .await?;
 
-pub fn create_coin_predicate(
-    coin: Coin,
-    asset_id: AssetId,
-    code: Vec<u8>,
-    predicate_data: Vec<u8>,
-) -> FuelInput {
-    FuelInput::coin_predicate(
-        coin.utxo_id,
-        coin.owner.into(),
-        coin.amount,
-        asset_id,
-        TxPointer::default(),
-        0u32.into(),
-        0u64,
-        code,
-        predicate_data,
-    )
-}
+        let dry_run_txs = dry_runner.txs.lock().expect("poisoned lock");
+        let FuelTransaction::Script(dry_run_tx) = &dry_run_txs[0] else {
+            panic!("expected a script to be dry run");
+        };
+        let temporary_coin = dry_run_tx.inputs().last().expect("has a temporary coin");
 
-pub fn create_coin_message_predicate(
-    message: Message,
-    code: Vec<u8>,
-    predicate_data: Vec<u8>,
-) -> FuelInput {
-    if message.data.is_empty() {
-        FuelInput::message_coin_predicate(
-            message.sender.into(),
-            message.recipient.into(),
-            message.amount,
-            message.nonce,
-            0u64,
-            code,
-            predicate_data,
-        )
-    } else {
-        FuelInput::message_data_predicate(
-            message.sender.into(),
-            message.recipient.into(),
-            message.amount,
-            message.nonce,
-            0u64,
-            message.data,
-            code,
-            predicate_data,
-        )
+        assert_eq!(
+            temporary_coin.asset_id(&BASE_ASSET_ID),
+            Some(&BASE_ASSET_ID)
+        );
+        assert!(tx.inputs().is_empty());
+
+        Ok(())
     }
-}
 
-fn generate_missing_witnesses(
-    id: Bytes32,
-    unresolved_signatures: &UnresolvedSignatures,
-) -> Vec<Witness> {
-    unresolved_signatures
-        .secret_keys
-        .iter()
-        .map(|secret_key| {
-            let message = CryptoMessage::from_bytes(*id);
-            let signature = Signature::sign(secret_key, &message);
+    #[derive(Default)]
+    struct CountingDryRunner {
+        count: AtomicUsize,
+    }
 
-            Witness::from(signature.as_ref())
-        })
-        .collect()
-}
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl DryRunner for CountingDryRunner {
+        async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(100)
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::{bech32::Bech32Address, message::MessageStatus};
+    struct VaryingDryRunner {
+        gas_used: Vec<u64>,
+        count: AtomicUsize,
+    }
 
-    #[test]
-    fn storage_slots_are_sorted_when_set() {
-        let unsorted_storage_slots = [2, 1].map(given_a_storage_slot).to_vec();
-        let sorted_storage_slots = [1, 2].map(given_a_storage_slot).to_vec();
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    impl DryRunner for VaryingDryRunner {
+        async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
+            let run = self.count.fetch_add(1, Ordering::SeqCst);
+            Ok(self.gas_used[run % self.gas_used.len()])
+        }
+    }
 
-        let network_info = NetworkInfo {
-            min_gas_price: 0,
-            consensus_parameters: Default::default(),
+    #[tokio::test]
+    async fn calibrated_tolerance_covers_the_gas_used_spread() -> Result<()> {
+        let runner = VaryingDryRunner {
+            gas_used: vec![100, 110, 105],
+            count: AtomicUsize::new(0),
         };
-        let builder =
-            CreateTransactionBuilder::new(network_info).with_storage_slots(unsorted_storage_slots);
+        let tb = ScriptTransactionBuilder::new(given_network_info())
+            .with_script(vec![op::ret(RegId::ONE)].into_iter().collect());
 
-        assert_eq!(builder.storage_slots, sorted_storage_slots);
-    }
+        let tolerance = tb.calibrate_gas_tolerance(&runner, 6).await?;
 
-    fn given_a_storage_slot(key: u8) -> StorageSlot {
-        let mut bytes_32 = Bytes32::zeroed();
-        bytes_32[0] = key;
+        assert_eq!(runner.count.load(Ordering::SeqCst), 6);
+        assert!((tolerance - 0.11).abs() < 1e-6);
+        assert!(tb.calibrate_gas_tolerance(&runner, 0).await.is_err());
 
-        StorageSlot::new(bytes_32, Default::default())
+        Ok(())
     }
 
     #[test]
@@ -960,14 +7860,91 @@ mod tests {
     }
 
     fn given_a_message(data: Vec<u8>) -> Message {
-        Message {
-            sender: Bech32Address::default(),
-            recipient: Bech32Address::default(),
-            nonce: 0.into(),
-            amount: 0,
+        Message::new(
+            Bech32Address::default(),
+            Bech32Address::default(),
+            Nonce::zeroed(),
+            0,
             data,
-            da_height: 0,
-            status: MessageStatus::Unspent,
+        )
+    }
+
+    fn given_a_predicate_artifact() -> Result<PredicateArtifact> {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+        PredicateArtifact::load_from(
+            format!("{fixtures}/predicate_configurables.bin"),
+            format!("{fixtures}/predicate_configurables-abi.json"),
+        )
+    }
+
+    fn given_network_info() -> NetworkInfo {
+        NetworkInfo {
+            min_gas_price: 0,
+            consensus_parameters: Default::default(),
+        }
+    }
+
+    fn given_a_secret_key(seed: u8) -> SecretKey {
+        SecretKey::from_str(&format!("{seed:064x}")).expect("should be a valid secret key")
+    }
+
+    fn given_a_base_asset_transfer(outputs: Vec<Output>) -> ScriptTransactionBuilder {
+        let secret_key = given_a_secret_key(1);
+        let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+        let coin = Coin {
+            amount: 1_000,
+            owner: owner.clone(),
+            ..Default::default()
+        };
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            vec![Input::resource_signed(CoinType::Coin(coin))],
+            outputs,
+            TxPolicies::default().with_gas_price(1),
+            given_network_info(),
+        );
+        tb.add_unresolved_signature(owner, secret_key);
+
+        tb
+    }
+
+    fn given_a_signed_transfer(owner: &Bech32Address) -> ScriptTransactionBuilder {
+        ScriptTransactionBuilder::prepare_transfer(
+            vec![given_a_coin_input(100, owner.clone())],
+            vec![Output::change(owner.into(), 0, BASE_ASSET_ID)],
+            TxPolicies::default(),
+            given_network_info(),
+        )
+    }
+
+    fn given_a_coin_input(amount: u64, owner: Bech32Address) -> Input {
+        let coin = Coin {
+            amount,
+            owner,
+            ..Default::default()
+        };
+
+        Input::resource_signed(CoinType::Coin(coin))
+    }
+
+    fn given_a_predicate(code: Vec<u8>, data: UnresolvedBytes) -> Input {
+        let coin = Coin {
+            utxo_id: UtxoId::new([1; 32].into(), 0),
+            amount: 100,
+            asset_id: BASE_ASSET_ID,
+            ..Default::default()
+        };
+
+        Input::resource_predicate(CoinType::Coin(coin), code, data)
+    }
+
+    fn given_a_predicate_input(predicate_gas_used: Option<u64>) -> Input {
+        let input = given_a_predicate(vec![1, 2, 3], UnresolvedBytes::default());
+
+        match predicate_gas_used {
+            Some(gas) => input.with_predicate_gas_used(gas),
+            None => input,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::constants::WORD_SIZE;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Data {
     // Write the enclosed data immediately.
     Inline(Vec<u8>),
@@ -20,7 +20,7 @@ impl Data {
 // To get the final encoded bytes, we need to know the address at which these
 // bytes are going to be loaded at. Once the address is given to `resolve`
 // normal bytes can be retrieved.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
 pub struct UnresolvedBytes {
     data: Vec<Data>,
 }
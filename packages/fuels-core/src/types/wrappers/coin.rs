@@ -8,14 +8,14 @@ use fuel_core_client::client::types::{
 
 use crate::types::bech32::Bech32Address;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
 pub enum CoinStatus {
     #[default]
     Unspent,
     Spent,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Coin {
     pub amount: u64,
     pub block_created: u32,
@@ -8,7 +8,7 @@ use crate::{
     types::{bech32::Bech32Address, coin::Coin, coin_type_id::CoinTypeId, message::Message},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum CoinType {
     Coin(Coin),
     Message(Message),
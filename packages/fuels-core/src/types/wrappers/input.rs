@@ -7,7 +7,7 @@ use fuel_types::{AssetId, Bytes32, ContractId};
 
 use crate::types::{coin_type::CoinType, unresolved_bytes::UnresolvedBytes};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Input {
     ResourceSigned {
         resource: CoinType,
@@ -16,6 +16,7 @@ pub enum Input {
         resource: CoinType,
         code: Vec<u8>,
         data: UnresolvedBytes,
+        predicate_gas_used: Option<u64>,
     },
     Contract {
         utxo_id: UtxoId,
@@ -40,9 +41,23 @@ impl Input {
             resource,
             code,
             data,
+            predicate_gas_used: None,
         }
     }
 
+    /// Sets the gas used by the predicate, e.g. when it was estimated ahead of time.
+    /// Has no effect on other inputs.
+    pub fn with_predicate_gas_used(mut self, gas: u64) -> Self {
+        if let Self::ResourcePredicate {
+            predicate_gas_used, ..
+        } = &mut self
+        {
+            *predicate_gas_used = Some(gas);
+        }
+
+        self
+    }
+
     pub fn amount(&self) -> Option<u64> {
         match self {
             Self::ResourceSigned { resource, .. } | Self::ResourcePredicate { resource, .. } => {
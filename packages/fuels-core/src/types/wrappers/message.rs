@@ -8,14 +8,14 @@ use fuel_tx::{Input, MessageId};
 
 use crate::types::bech32::Bech32Address;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
 pub enum MessageStatus {
     #[default]
     Unspent,
     Spent,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Message {
     pub amount: u64,
     pub sender: Bech32Address,
@@ -27,6 +27,45 @@ pub struct Message {
 }
 
 impl Message {
+    /// Creates an unspent `Message` with an explicit nonce, e.g. to build deterministic
+    /// message inputs in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fuels_core::types::{
+    ///     bech32::Bech32Address, coin_type::CoinType, input::Input, message::Message, Nonce,
+    /// };
+    ///
+    /// let message = Message::new(
+    ///     Bech32Address::default(),
+    ///     Bech32Address::default(),
+    ///     Nonce::new([1; 32]),
+    ///     100,
+    ///     vec![1, 2, 3],
+    /// );
+    /// let input = Input::resource_signed(CoinType::Message(message));
+    ///
+    /// assert!(input.contains_data());
+    /// ```
+    pub fn new(
+        sender: Bech32Address,
+        recipient: Bech32Address,
+        nonce: Nonce,
+        amount: u64,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            amount,
+            sender,
+            recipient,
+            nonce,
+            data,
+            da_height: 0,
+            status: MessageStatus::Unspent,
+        }
+    }
+
     pub fn message_id(&self) -> MessageId {
         Input::compute_message_id(
             &(&self.sender).into(),
@@ -2,8 +2,8 @@ use std::{collections::HashMap, fmt::Debug};
 
 use fuel_tx::{
     field::{
-        GasPrice, Inputs, Maturity, MintAmount, MintAssetId, Outputs, Script as ScriptField,
-        ScriptData, ScriptGasLimit, Witnesses,
+        GasPrice, Inputs, Maturity, MintAmount, MintAssetId, Outputs, Policies,
+        Script as ScriptField, ScriptData, ScriptGasLimit, Witnesses,
     },
     input::{
         coin::{CoinPredicate, CoinSigned},
@@ -11,6 +11,7 @@ use fuel_tx::{
             MessageCoinPredicate, MessageCoinSigned, MessageDataPredicate, MessageDataSigned,
         },
     },
+    policies::PolicyType,
     Buildable, Bytes32, Cacheable, Chargeable, ConsensusParameters, Create, FormatValidityChecks,
     Input, Mint, Output, Salt as FuelSalt, Script, StorageSlot, Transaction as FuelTransaction,
     TransactionFee, UniqueIdentifier, Witness,
@@ -195,6 +196,12 @@ pub trait Transaction:
 
     fn with_gas_price(self, gas_price: u64) -> Self;
 
+    /// The `MaxFee` policy, if set.
+    fn max_fee(&self) -> Option<u64>;
+
+    /// The `WitnessLimit` policy, if set.
+    fn witness_limit(&self) -> Option<u64>;
+
     fn metered_bytes_size(&self) -> usize;
 
     fn inputs(&self) -> &Vec<Input>;
@@ -203,6 +210,10 @@ pub trait Transaction:
 
     fn witnesses(&self) -> &Vec<Witness>;
 
+    /// Number of witnesses in the transaction, both the user provided ones and the
+    /// signatures generated during build.
+    fn witness_count(&self) -> usize;
+
     fn is_using_predicates(&self) -> bool;
 
     /// Precompute transaction metadata. The metadata is required for
@@ -338,6 +349,14 @@ macro_rules! impl_tx_wrapper {
                 self
             }
 
+            fn max_fee(&self) -> Option<u64> {
+                self.tx.policies().get(PolicyType::MaxFee)
+            }
+
+            fn witness_limit(&self) -> Option<u64> {
+                self.tx.policies().get(PolicyType::WitnessLimit)
+            }
+
             fn metered_bytes_size(&self) -> usize {
                 self.tx.metered_bytes_size()
             }
@@ -354,6 +373,10 @@ macro_rules! impl_tx_wrapper {
                 self.tx.witnesses()
             }
 
+            fn witness_count(&self) -> usize {
+                self.tx.witnesses().len()
+            }
+
             fn is_using_predicates(&self) -> bool {
                 self.is_using_predicates
             }
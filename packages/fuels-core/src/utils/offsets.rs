@@ -42,6 +42,12 @@ pub fn coin_predicate_data_offset(code_len: usize) -> usize {
         + padded_len_usize(code_len)
 }
 
+/// Serialized size of a coin predicate input with the given `code` and `data_len`
+/// bytes of resolved predicate data, e.g. to compare its fee against a signed input.
+pub fn predicate_input_size(code: &[u8], data_len: usize) -> usize {
+    coin_predicate_data_offset(code.len()) + padded_len_usize(data_len)
+}
+
 pub fn message_predicate_data_offset(message_data_len: usize, code_len: usize) -> usize {
     InputRepr::Message
         .data_offset()
@@ -70,3 +76,31 @@ pub fn contract_input_offset() -> usize {
     // this last one.
     InputRepr::Contract.contract_id_offset().unwrap() + ContractId::LEN
 }
+
+#[cfg(test)]
+mod tests {
+    use fuel_tx::{Input, TxPointer};
+    use fuel_types::canonical::Serialize;
+
+    use super::*;
+
+    #[test]
+    fn predicate_input_size_matches_the_serialized_input() {
+        let code = vec![1; 13];
+        let data = vec![2; 21];
+
+        let input = Input::coin_predicate(
+            Default::default(),
+            Default::default(),
+            100,
+            Default::default(),
+            TxPointer::default(),
+            0u32.into(),
+            0,
+            code.clone(),
+            data.clone(),
+        );
+
+        assert_eq!(predicate_input_size(&code, data.len()), input.size());
+    }
+}
@@ -0,0 +1,117 @@
+//! Measures the allocations of a fee check against building a clone of the builder.
+//! Kept in its own test binary since it replaces the global allocator.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use fuel_asm::{op, RegId};
+use fuel_crypto::{PublicKey, SecretKey};
+use fuel_tx::{ConsensusParameters, Transaction as FuelTransaction, Witness};
+use fuels_core::{
+    constants::BASE_ASSET_ID,
+    types::{
+        bech32::Bech32Address,
+        coin::Coin,
+        coin_type::CoinType,
+        errors::Result,
+        input::Input,
+        transaction::{Transaction, TxPolicies},
+        transaction_builders::{
+            BuildableTransaction, DryRunner, NetworkInfo, ScriptTransactionBuilder,
+            TransactionBuilder,
+        },
+    },
+};
+
+// Counts the bytes allocated by the current thread, `tokio::test` runs on a single
+// threaded runtime
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATED_BYTES.try_with(|bytes| bytes.set(bytes.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocated_bytes() -> usize {
+    ALLOCATED_BYTES.with(Cell::get)
+}
+
+#[derive(Clone, Copy)]
+struct FixedGasDryRunner;
+
+#[async_trait::async_trait]
+impl DryRunner for FixedGasDryRunner {
+    async fn dry_run_and_get_used_gas(&self, _: FuelTransaction, _: f32) -> Result<u64> {
+        Ok(100)
+    }
+}
+
+fn given_a_large_transfer() -> ScriptTransactionBuilder {
+    let secret_key = SecretKey::try_from([1u8; 32].as_slice()).expect("should be a valid key");
+    let owner = Bech32Address::new("fuel", PublicKey::from(&secret_key).hash());
+    let coin = Coin {
+        amount: 1_000,
+        asset_id: BASE_ASSET_ID,
+        owner: owner.clone(),
+        ..Default::default()
+    };
+    let network_info = NetworkInfo {
+        min_gas_price: 0,
+        consensus_parameters: ConsensusParameters::default(),
+    };
+
+    let mut tb = ScriptTransactionBuilder::prepare_transfer(
+        vec![Input::resource_signed(CoinType::Coin(coin))],
+        vec![],
+        TxPolicies::default().with_gas_price(1),
+        network_info,
+    )
+    .with_script(vec![op::ret(RegId::ONE)].into_iter().collect())
+    .with_script_data(vec![1u8; 10_000])
+    .with_witnesses(vec![Witness::from(vec![1u8; 10_000])]);
+    tb.add_unresolved_signature(owner, secret_key);
+
+    tb
+}
+
+#[tokio::test]
+async fn fee_check_allocates_less_than_building_a_clone() -> Result<()> {
+    let tb = given_a_large_transfer();
+
+    let allocated_before = allocated_bytes();
+    let fee = tb
+        .fee_checked_from_tx(FixedGasDryRunner)
+        .await?
+        .expect("should compute the fee");
+    let fee_check_bytes = allocated_bytes() - allocated_before;
+
+    let allocated_before = allocated_bytes();
+    let tx = tb.clone().build(FixedGasDryRunner).await?;
+    let build_bytes = allocated_bytes() - allocated_before;
+
+    let built_fee = tx
+        .fee_checked_from_tx(tb.consensus_parameters())
+        .expect("should compute the fee");
+    assert_eq!(fee.max_fee(), built_fee.max_fee());
+    assert!(
+        fee_check_bytes < build_bytes,
+        "fee check allocated {fee_check_bytes} bytes, building a clone {build_bytes}"
+    );
+
+    Ok(())
+}